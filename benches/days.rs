@@ -0,0 +1,35 @@
+use advent_of_code_2019::{find_day, module::read_module_input};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_part(c: &mut Criterion, day_nr: u8, part: &'static str) {
+    let day = find_day(day_nr).expect("unknown day");
+    let input = read_module_input(day.name).expect("cannot read input");
+    let input = input.trim();
+    c.bench_function(&format!("{}_{}", day.name, part), |b| {
+        b.iter(|| (day.solve_part)(part, black_box(input)).unwrap().unwrap())
+    });
+}
+
+fn day03(c: &mut Criterion) {
+    bench_part(c, 3, "pt1");
+    bench_part(c, 3, "pt2");
+}
+
+fn day09(c: &mut Criterion) {
+    bench_part(c, 9, "pt1");
+    bench_part(c, 9, "pt2");
+}
+
+// Part 2 searches each of the four quadrants on a thread of its own
+fn day18(c: &mut Criterion) {
+    bench_part(c, 18, "pt1");
+    bench_part(c, 18, "pt2");
+}
+
+// Tries the item combinations at the checkpoint across all threads
+fn day25(c: &mut Criterion) {
+    bench_part(c, 25, "pt1");
+}
+
+criterion_group!(benches, day03, day09, day18, day25);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// What went wrong, each kind exits with its own code so scripts can tell
+/// them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The arguments couldn't be parsed.
+    Usage = 2,
+    /// The session token or the profiles config couldn't be read.
+    Token = 3,
+    /// The request failed, or the server responded with an unexpected status.
+    Http = 4,
+    /// The puzzle isn't unlocked yet.
+    Unavailable = 5,
+    /// The server rejected the session token.
+    Unauthorized = 6,
+    /// Reading or writing a local file failed.
+    Io = 7,
+    /// The post-download hook couldn't be started, or it failed.
+    Hook = 8,
+    /// The solver returned an error for one of the parts.
+    Solver = 9,
+    /// The submitted answer was wrong.
+    WrongAnswer = 10,
+    /// An answer was submitted too recently.
+    RateLimited = 11,
+    /// The answer wasn't submitted, because it's known to be pointless.
+    AlreadySubmitted = 12,
+}
+
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new<M: Into<String>>(kind: ErrorKind, message: M) -> Error {
+        Error {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        self.kind as u8
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,81 @@
+//! What to do with an input once it's downloaded.
+
+use crate::error::{Error, ErrorKind, Result};
+use advent_of_code_2019::Day;
+use std::process::Command;
+
+pub const DEFAULT_HOOK: &str = "code {path}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hook {
+    None,
+    /// Opens the input in `$EDITOR`.
+    Editor,
+    /// A shell command, in which `{path}` is replaced by the path of the input,
+    /// and `{day}` by the number of the day.
+    Command(String),
+}
+
+impl Hook {
+    pub fn parse(arg: &str) -> Hook {
+        match arg {
+            "none" => Hook::None,
+            "editor" => Hook::Editor,
+            template => Hook::Command(template.to_owned()),
+        }
+    }
+
+    /// The command line to run for the input, if any.
+    fn command_line(&self, path: &str, day: &Day) -> Result<Option<String>> {
+        let template = match self {
+            Hook::None => return Ok(None),
+            Hook::Editor => match std::env::var("EDITOR") {
+                Ok(editor) if !editor.trim().is_empty() => format!("{} {{path}}", editor),
+                _ => return Err(Error::new(ErrorKind::Hook, "$EDITOR isn't set")),
+            },
+            Hook::Command(template) => template.clone(),
+        };
+        Ok(Some(
+            template
+                .replace("{path}", path)
+                .replace("{day}", &day.number.to_string()),
+        ))
+    }
+
+    /// Runs the hook through the shell, and waits for it to finish.
+    pub fn run(&self, path: &str, day: &Day) -> Result<()> {
+        let command_line = match self.command_line(path, day)? {
+            Some(command_line) => command_line,
+            None => return Ok(()),
+        };
+        let status = if cfg!(target_os = "windows") {
+            Command::new("cmd").arg("/C").arg(&command_line).status()
+        } else {
+            Command::new("sh").arg("-c").arg(&command_line).status()
+        };
+        match status {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(Error::new(
+                ErrorKind::Hook,
+                format!("hook {:?} failed with {}", command_line, status),
+            )),
+            Err(err) => Err(Error::new(
+                ErrorKind::Hook,
+                format!("couldn't start hook {:?}\n{:?}", command_line, err),
+            )),
+        }
+    }
+}
+
+#[test]
+fn placeholders() {
+    let day = advent_of_code_2019::find_day(7).unwrap();
+    let hook = Hook::parse("vim {path} # day {day}");
+    assert_eq!(
+        hook.command_line("./data/day07.txt", day)
+            .unwrap()
+            .as_deref(),
+        Some("vim ./data/day07.txt # day 7")
+    );
+    assert_eq!(Hook::parse("none").command_line("x", day).unwrap(), None);
+}
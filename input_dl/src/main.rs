@@ -1,70 +1,370 @@
-use reqwest::{blocking::Client, StatusCode};
-use std::process::Command;
-
-fn main() {
-    let day_nr: u8 = match std::env::args()
-        .skip(1)
-        .next()
-        .and_then(|day| day.parse().ok())
-    {
-        Some(x) => x,
-        None => {
-            eprintln!("expected 1 argument with the day number");
-            std::process::exit(-1);
+mod error;
+mod hook;
+mod puzzle;
+mod submit;
+
+use advent_of_code_2019::{find_day, module::*, Day};
+use error::{Error, ErrorKind, Result};
+use hook::Hook;
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    StatusCode,
+};
+use std::path::Path;
+use std::process::ExitCode;
+
+const DEFAULT_YEAR: u16 = 2019;
+const PROFILES_PATH: &str = "./profiles.txt";
+const USAGE: &str = "usage: input_dl <day> [--force] [--run] [--hook <hook> | --no-open] [options]
+       input_dl submit <day> <part> <answer> [options]
+hooks: none, editor, or a shell command with {path} and {day} placeholders
+options: --year <year> --profile <name> --base-url <url>";
+
+struct Options {
+    day: &'static Day,
+    /// Submit an answer instead of downloading the input.
+    submit: Option<Submission>,
+    /// Download the input even if it was downloaded before.
+    force: bool,
+    /// What to do with the input once it's downloaded.
+    hook: Hook,
+    /// Run the solver on the input once it's downloaded.
+    run: bool,
+    /// Base URL of the event, the input is at `{base_url}/day/{day}/input`.
+    base_url: String,
+    year: u16,
+    /// The account to use, the default one uses `token.txt`.
+    profile: Option<String>,
+}
+
+impl Options {
+    /// Where the files shared by every profile, such as the examples, are stored.
+    fn event_dir(&self) -> String {
+        if self.year == DEFAULT_YEAR {
+            "./data".to_owned()
+        } else {
+            format!("./data/{}", self.year)
         }
-    };
+    }
 
-    let url = format!("https://adventofcode.com/2019/day/{}/input", day_nr);
-    let token = match std::fs::read_to_string("./token.txt") {
-        Ok(token) => token,
-        Err(err) => {
-            eprintln!("cannot read token\n{:?}", err);
-            std::process::exit(-1);
+    /// Where the inputs, answers and submissions of the profile are stored.
+    fn profile_dir(&self) -> String {
+        match &self.profile {
+            Some(profile) => format!("{}/{}", self.event_dir(), profile),
+            None => self.event_dir(),
+        }
+    }
+}
+
+struct Submission {
+    part: u8,
+    answer: String,
+}
+
+fn parse_day(arg: &str) -> std::result::Result<&'static Day, String> {
+    let number = arg.parse().map_err(|_| format!("invalid day {:?}", arg))?;
+    find_day(number).ok_or(format!("day {} isn't solved", number))
+}
+
+fn parse_args<I>(mut args: I) -> std::result::Result<Options, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut positional = Vec::new();
+    let mut force = false;
+    let mut hook = Hook::parse(hook::DEFAULT_HOOK);
+    let mut run = false;
+    let mut base_url = None;
+    let mut year = DEFAULT_YEAR;
+    let mut profile = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--force" => force = true,
+            "--no-open" => hook = Hook::None,
+            "--hook" => hook = Hook::parse(&args.next().ok_or("expected a hook after --hook")?),
+            "--run" => run = true,
+            "--base-url" => {
+                base_url = Some(args.next().ok_or("expected a URL after --base-url")?);
+            }
+            "--year" => {
+                let arg = args.next().ok_or("expected a year after --year")?;
+                year = match arg.parse() {
+                    Ok(year) if year >= 2015 => year,
+                    _ => return Err(format!("invalid year {:?}", arg)),
+                };
+            }
+            "--profile" => {
+                let arg = args.next().ok_or("expected a name after --profile")?;
+                if !is_profile_name(&arg) {
+                    return Err(format!("invalid profile name {:?}", arg));
+                }
+                profile = Some(arg);
+            }
+            _ if !arg.starts_with("--") => positional.push(arg),
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    let (day, submit) = match positional.as_slice() {
+        [day] => (parse_day(day)?, None),
+        [command, day, part, answer] if command == "submit" => {
+            let part = match part.trim_start_matches("pt") {
+                "1" => 1,
+                "2" => 2,
+                _ => return Err(format!("invalid part {:?}", part)),
+            };
+            let answer = answer.clone();
+            (parse_day(day)?, Some(Submission { part, answer }))
         }
+        [] => return Err("expected the day number".to_owned()),
+        [_, rest @ ..] => return Err(format!("unexpected argument {:?}", rest[0])),
     };
+    if run && year != DEFAULT_YEAR {
+        return Err(format!("there are no solvers for {}", year));
+    }
+
+    Ok(Options {
+        day,
+        submit,
+        force,
+        hook,
+        run,
+        base_url: base_url
+            .map(|url| url.trim_end_matches('/').to_owned())
+            .unwrap_or_else(|| format!("https://adventofcode.com/{}", year)),
+        year,
+        profile,
+    })
+}
+
+/// Profiles become directories next to the examples and the inputs of other
+/// years, so their names can't be mistaken for either.
+fn is_profile_name(name: &str) -> bool {
+    name != "examples"
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && name.chars().any(|c| !c.is_ascii_digit())
+}
+
+/// Finds the token of a profile in the config file, which has a line per
+/// profile in the form `name = token`. Empty lines and `#` comments are ignored.
+fn find_token(config: &str, profile: &str) -> Option<String> {
+    config
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .find(|(name, _)| name.trim() == profile)
+        .map(|(_, token)| token.trim().to_owned())
+}
+
+fn read_token(options: &Options) -> Result<String> {
+    let token_error = |message| Error::new(ErrorKind::Token, message);
+    match &options.profile {
+        None => std::fs::read_to_string("./token.txt")
+            .map(|token| token.trim().to_owned())
+            .map_err(|err| token_error(format!("cannot read token\n{:?}", err))),
+        Some(profile) => {
+            let config = std::fs::read_to_string(PROFILES_PATH)
+                .map_err(|err| token_error(format!("cannot read {}\n{:?}", PROFILES_PATH, err)))?;
+            find_token(&config, profile).ok_or_else(|| {
+                token_error(format!(
+                    "no token for profile {:?} in {}",
+                    profile, PROFILES_PATH
+                ))
+            })
+        }
+    }
+}
+
+fn io_error(context: &str) -> impl FnOnce(std::io::Error) -> Error + '_ {
+    move |err| Error::new(ErrorKind::Io, format!("{}:\n{:?}", context, err))
+}
+
+/// Fetches `{base_url}{path}` with the session token.
+fn fetch(options: &Options, path: &str) -> Result<String> {
+    let url = format!("{}{}", options.base_url, path);
+    send(options, Client::new().get(&url))
+}
 
-    let client = Client::new();
-    let resp = match client
-        .get(&url)
+/// Sends the request with the session token, and returns the response text.
+fn send(options: &Options, request: RequestBuilder) -> Result<String> {
+    let token = read_token(options)?;
+    let resp = request
         .header("cookie", format!("session={}", token))
         .send()
-    {
-        Ok(resp) => resp,
-        Err(err) => {
-            eprintln!("http error:\n{:?}", err);
-            std::process::exit(-1);
+        .map_err(|err| Error::new(ErrorKind::Http, format!("http error:\n{:?}", err)))?;
+    match resp.status() {
+        StatusCode::OK => {}
+        StatusCode::NOT_FOUND => {
+            return Err(Error::new(
+                ErrorKind::Unavailable,
+                format!("day {} isn't available yet", options.day.number),
+            ));
         }
-    };
-    if resp.status() != StatusCode::OK {
-        eprintln!("expected HTTP 200 OK status, got {:?}", resp.status());
-        std::process::exit(-1);
+        StatusCode::BAD_REQUEST => {
+            return Err(Error::new(
+                ErrorKind::Unauthorized,
+                "the session token was rejected, it may have expired",
+            ));
+        }
+        status => {
+            return Err(Error::new(
+                ErrorKind::Http,
+                format!("expected HTTP 200 OK status, got {:?}", status),
+            ));
+        }
+    }
+
+    resp.text()
+        .map_err(|err| Error::new(ErrorKind::Http, format!("http text error:\n{:?}", err)))
+}
+
+fn download(options: &Options, file_path: &str) -> Result<()> {
+    let text = fetch(options, &format!("/day/{}/input", options.day.number))?;
+    std::fs::create_dir_all(options.profile_dir())
+        .and_then(|_| std::fs::write(file_path, text))
+        .map_err(io_error("couldn't write downloaded input to file"))
+}
+
+/// Saves the examples and the answers found on the puzzle page. The examples
+/// are staged until they're given answers, since not every block on the page
+/// is an input.
+fn extract_puzzle(options: &Options) -> Result<()> {
+    let html = fetch(options, &format!("/day/{}", options.day.number))?;
+    let puzzle = puzzle::parse_puzzle(&html);
+    let examples_dir = format!(
+        "{}/examples/{}/{}",
+        options.event_dir(),
+        puzzle::STAGED_DIR,
+        options.day.name
+    );
+    let answers_path = format!("{}/{}", options.profile_dir(), puzzle::ANSWERS_FILE);
+    let written = puzzle::write_examples(&examples_dir, &puzzle.examples)
+        .map_err(io_error("couldn't write the examples"))?;
+    if written > 0 {
+        println!(
+            "saved {} new example(s) for {} in {}",
+            written, options.day.name, examples_dir
+        );
+    }
+    if !puzzle.answers.is_empty() {
+        puzzle::update_answers(&answers_path, options.day, &puzzle.answers)
+            .map_err(io_error("couldn't write the answers"))?;
+    }
+    Ok(())
+}
+
+/// Runs every part of the day on the input, in the same way the runner does.
+fn run_solver(day: &Day, file_path: &str) -> Result<()> {
+    let mut ran = false;
+    let mut failed = 0;
+    execute_module_at(day.name, file_path, |input, mut closure| {
+        ran = true;
+        (day.run)(input, &mut |_| true, &mut |msg| {
+            if let Message::Finish(_, Err(_)) = msg {
+                failed += 1;
+            }
+            execute_module_callback(&mut closure, msg)
+        })
+    });
+    match (ran, failed) {
+        (false, _) => Err(Error::new(ErrorKind::Io, "couldn't read the input")),
+        (true, 0) => Ok(()),
+        (true, failed) => Err(Error::new(
+            ErrorKind::Solver,
+            format!("{} part(s) of {} failed", failed, day.name),
+        )),
+    }
+}
+
+fn submit(options: &Options, submission: &Submission) -> Result<()> {
+    let (day, part, answer) = (options.day, submission.part, submission.answer.as_str());
+    let submissions_path = format!("{}/{}", options.profile_dir(), submit::SUBMISSIONS_FILE);
+    let answers_path = format!("{}/{}", options.profile_dir(), puzzle::ANSWERS_FILE);
+    let mut submissions = submit::Submissions::load(&submissions_path)
+        .map_err(io_error("couldn't read the earlier submissions"))?;
+    if let Some(previous) = submissions.previous(day, part, answer) {
+        return Err(Error::new(
+            ErrorKind::AlreadySubmitted,
+            format!("not submitting, already submitted: {}", previous),
+        ));
     }
 
-    let text = match resp.text() {
-        Ok(text) => text,
+    let url = format!("{}/day/{}/answer", options.base_url, day.number);
+    let level = part.to_string();
+    let request = Client::new()
+        .post(&url)
+        .form(&[("level", level.as_str()), ("answer", answer)]);
+    let outcome = submit::parse_outcome(&send(options, request)?);
+    println!("{} pt{} {}: {}", day.name, part, answer, outcome);
+
+    submissions.record(day, part, answer, &outcome);
+    submissions
+        .save(&submissions_path)
+        .map_err(io_error("couldn't record the submission"))?;
+    match outcome {
+        submit::Outcome::Correct => puzzle::record_answers(&answers_path, day, &[(part, answer)])
+            .map_err(io_error("couldn't record the answer")),
+        submit::Outcome::Wrong(_) => Err(Error::new(ErrorKind::WrongAnswer, "wrong answer")),
+        submit::Outcome::RateLimited(_) => Err(Error::new(ErrorKind::RateLimited, "rate limited")),
+        submit::Outcome::WrongLevel => Err(Error::new(
+            ErrorKind::AlreadySubmitted,
+            "the part is already solved or still locked",
+        )),
+        submit::Outcome::Unknown(_) => Err(Error::new(
+            ErrorKind::Http,
+            "the response wasn't recognized",
+        )),
+    }
+}
+
+fn run(options: &Options) -> Result<()> {
+    if let Some(submission) = &options.submit {
+        return submit(options, submission);
+    }
+
+    let file_path = format!("{}/{}.txt", options.profile_dir(), options.day.name);
+    if options.force || !Path::new(&file_path).exists() {
+        download(options, &file_path)?;
+        extract_puzzle(options)?;
+    } else {
+        println!(
+            "{} already exists, use --force to download it again",
+            file_path
+        );
+    }
+
+    if options.run {
+        run_solver(options.day, &file_path)?;
+    }
+    options.hook.run(&file_path, options.day)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
         Err(err) => {
-            eprintln!("http text error:\n{:?}", err);
-            std::process::exit(-1);
+            eprintln!("{}\n{}", err, USAGE);
+            return ExitCode::from(ErrorKind::Usage as u8);
         }
     };
-    let file_path = format!("./data/day{:0>2}.txt", day_nr);
-    match std::fs::create_dir_all("./data/").and_then(|_| std::fs::write(&file_path, text)) {
-        Ok(()) => {}
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
         Err(err) => {
-            eprintln!("couldn't write downloaded input to file:\n{:?}", err);
-            std::process::exit(-1);
+            eprintln!("{}", err);
+            ExitCode::from(err.exit_code())
         }
     }
+}
 
-    let _ = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .arg("/C")
-            .arg(&format!("code {}", file_path))
-            .spawn()
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&format!("code {}", file_path))
-            .spawn()
-    };
+#[test]
+fn profile_tokens() {
+    let config = "# accounts\nmain = abc\n\n  alt=def  \nbroken\n";
+    assert_eq!(find_token(config, "main").as_deref(), Some("abc"));
+    assert_eq!(find_token(config, "alt").as_deref(), Some("def"));
+    assert_eq!(find_token(config, "broken"), None);
+    assert_eq!(find_token(config, "other"), None);
 }
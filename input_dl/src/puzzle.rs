@@ -0,0 +1,185 @@
+//! Extracts the examples and the answers from a puzzle page.
+
+use advent_of_code_2019::Day;
+use std::path::Path;
+
+/// The name of the answers manifest, which is stored next to the inputs.
+pub const ANSWERS_FILE: &str = "answers.txt";
+/// The directory in the examples in which new examples are written. The
+/// tests only look at the directories named after the days, so the examples
+/// are only checked once they're moved there with their answers.
+pub const STAGED_DIR: &str = "staged";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The contents of every `<pre><code>` block, in the order they appear.
+    pub examples: Vec<String>,
+    /// The accepted answers, the first one is for part 1.
+    pub answers: Vec<String>,
+}
+
+pub fn parse_puzzle(html: &str) -> Puzzle {
+    let examples = between(html, "<pre><code>", "</code></pre>")
+        .map(|block| decode_entities(&strip_tags(block)))
+        .collect();
+    let answers = between(html, "Your puzzle answer was <code>", "</code>")
+        .map(|answer| decode_entities(&strip_tags(answer)).trim().to_owned())
+        .collect();
+    Puzzle { examples, answers }
+}
+
+/// Every piece of text that sits between `start` and the first `end` after it.
+fn between<'a>(
+    mut html: &'a str,
+    start: &'a str,
+    end: &'a str,
+) -> impl Iterator<Item = &'a str> + 'a {
+    std::iter::from_fn(move || {
+        let from = html.find(start)? + start.len();
+        let to = from + html[from..].find(end)?;
+        let found = &html[from..to];
+        html = &html[to + end.len()..];
+        Some(found)
+    })
+}
+
+/// Removes markup such as the `<em>` used to highlight parts of an example.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode_entities(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(idx) = rest.find('&') {
+        text.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                entity if entity.starts_with("#x") => {
+                    std::char::from_u32(u32::from_str_radix(&entity[2..], 16).ok()?)?
+                }
+                entity if entity.starts_with('#') => {
+                    std::char::from_u32(entity[1..].parse().ok()?)?
+                }
+                _ => return None,
+            };
+            Some((c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                text.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Writes the examples to `<dir>/exampleN.txt`, skipping the ones that already
+/// exist. Returns how many were written.
+pub fn write_examples(dir: &str, examples: &[String]) -> std::io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut written = 0;
+    for (idx, example) in examples.iter().enumerate() {
+        let path = format!("{}/example{}.txt", dir, idx + 1);
+        if Path::new(&path).exists() {
+            continue;
+        }
+        std::fs::write(path, example)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Records the answers of a day in the manifest, which has a line per part in
+/// the form `day01 pt1 3270179`.
+pub fn update_answers(path: &str, day: &Day, answers: &[String]) -> std::io::Result<()> {
+    let answers: Vec<_> = answers
+        .iter()
+        .enumerate()
+        .map(|(idx, answer)| (idx as u8 + 1, answer.as_str()))
+        .collect();
+    record_answers(path, day, &answers)
+}
+
+/// Records the answers for the given parts, replacing any that were known.
+pub fn record_answers(path: &str, day: &Day, answers: &[(u8, &str)]) -> std::io::Result<()> {
+    let manifest = match std::fs::read_to_string(path) {
+        Ok(manifest) => manifest,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let prefixes: Vec<_> = answers
+        .iter()
+        .map(|(part, _)| format!("{} pt{} ", day.name, part))
+        .collect();
+    let mut lines: Vec<String> = manifest
+        .lines()
+        .filter(|line| !line.is_empty() && !prefixes.iter().any(|p| line.starts_with(p)))
+        .map(ToOwned::to_owned)
+        .collect();
+    lines.extend(
+        answers
+            .iter()
+            .map(|(part, answer)| format!("{} pt{} {}", day.name, part, answer)),
+    );
+    lines.sort();
+
+    if let Some(dir) = Path::new(path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut manifest = lines.join("\n");
+    manifest.push('\n');
+    std::fs::write(path, manifest)
+}
+
+#[test]
+fn solved_page() {
+    let puzzle = parse_puzzle(include_str!("../tests/fixtures/day01.html"));
+    assert_eq!(puzzle.examples, ["12\n14\n1969\n100756\n"]);
+    assert_eq!(puzzle.answers, ["3270179", "4902420"]);
+}
+
+#[test]
+fn unsolved_page() {
+    let puzzle = parse_puzzle(include_str!("../tests/fixtures/day18.html"));
+    assert_eq!(
+        puzzle.examples,
+        [
+            "#########\n#b.A.@.a#\n#########\n",
+            "########################\n#f.D.E.e.C.b.A.@.a.B.c.#\n\
+             ######################.#\n#d.....................#\n\
+             ########################\n",
+        ]
+    );
+    assert!(puzzle.answers.is_empty());
+}
+
+#[test]
+fn entities() {
+    assert_eq!(
+        decode_entities("&lt;x&gt; &amp;&amp; &#39;a&#x27; &nbsp; & b"),
+        "<x> && 'a' &nbsp; & b"
+    );
+}
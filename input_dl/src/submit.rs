@@ -0,0 +1,198 @@
+//! Interprets the page returned after submitting an answer, and keeps track of
+//! the answers that were submitted before.
+
+use crate::puzzle::strip_tags;
+use advent_of_code_2019::Day;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+/// The name of the submissions record, which is stored next to the inputs.
+pub const SUBMISSIONS_FILE: &str = "submissions.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Hint),
+    RateLimited(Duration),
+    /// The part was already solved, or part 1 isn't solved yet.
+    WrongLevel,
+    /// The text of a response that isn't recognized.
+    Unknown(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong(Hint::TooHigh) => write!(f, "wrong, too high"),
+            Outcome::Wrong(Hint::TooLow) => write!(f, "wrong, too low"),
+            Outcome::Wrong(Hint::None) => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "rate limited, wait {:?}", wait),
+            Outcome::WrongLevel => write!(f, "the part is already solved or still locked"),
+            Outcome::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+pub fn parse_outcome(html: &str) -> Outcome {
+    let text = html
+        .find("<article>")
+        .and_then(|start| {
+            let article = &html[start..];
+            article.find("</article>").map(|end| &article[..end])
+        })
+        .unwrap_or(html);
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        Outcome::Wrong(if text.contains("your answer is too high") {
+            Hint::TooHigh
+        } else if text.contains("your answer is too low") {
+            Hint::TooLow
+        } else {
+            Hint::None
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(text).unwrap_or_default())
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(strip_tags(text).trim().to_owned())
+    }
+}
+
+/// Parses the `You have 1m 5s left to wait` part of a rate limited response.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    text[start..end]
+        .split_whitespace()
+        .try_fold(0, |seconds, part| {
+            let (value, scale) = if let Some(value) = part.strip_suffix('h') {
+                (value, 3600)
+            } else if let Some(value) = part.strip_suffix('m') {
+                (value, 60)
+            } else {
+                (part.strip_suffix('s')?, 1)
+            };
+            Some(seconds + value.parse::<u64>().ok()? * scale)
+        })
+        .map(Duration::from_secs)
+}
+
+/// Answers that were submitted before, stored with a line per submission in
+/// the form `day01 pt1 correct 3270179`. The answer comes last, since it can
+/// contain spaces.
+#[derive(Debug, Default)]
+pub struct Submissions {
+    lines: Vec<String>,
+}
+
+impl Submissions {
+    pub fn load(path: &str) -> std::io::Result<Submissions> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Ok(Submissions {
+                lines: text
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect(),
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        if let Some(dir) = Path::new(path).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        std::fs::write(path, text)
+    }
+
+    /// The result of an earlier submission that makes submitting `answer`
+    /// pointless: the part being solved already, or this exact answer being wrong.
+    pub fn previous(&self, day: &Day, part: u8, answer: &str) -> Option<&str> {
+        let part = format!("pt{}", part);
+        self.lines.iter().find_map(|line| {
+            let mut fields = line.splitn(4, ' ');
+            let (line_day, line_part, result, line_answer) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            if line_day != day.name || line_part != part {
+                return None;
+            }
+            if result == "correct" || line_answer == answer {
+                Some(line.as_str())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Records the outcome, if it says anything about the answer.
+    pub fn record(&mut self, day: &Day, part: u8, answer: &str, outcome: &Outcome) {
+        let result = match outcome {
+            Outcome::Correct => "correct",
+            Outcome::Wrong(Hint::TooHigh) => "too_high",
+            Outcome::Wrong(Hint::TooLow) => "too_low",
+            Outcome::Wrong(Hint::None) => "wrong",
+            _ => return,
+        };
+        self.lines
+            .push(format!("{} pt{} {} {}", day.name, part, result, answer));
+    }
+}
+
+#[test]
+fn outcomes() {
+    assert_eq!(
+        parse_outcome(include_str!("../tests/fixtures/answer_correct.html")),
+        Outcome::Correct
+    );
+    assert_eq!(
+        parse_outcome(include_str!("../tests/fixtures/answer_too_high.html")),
+        Outcome::Wrong(Hint::TooHigh)
+    );
+    assert_eq!(
+        parse_outcome(include_str!("../tests/fixtures/answer_too_low.html")),
+        Outcome::Wrong(Hint::TooLow)
+    );
+    assert_eq!(
+        parse_outcome(include_str!("../tests/fixtures/answer_rate_limited.html")),
+        Outcome::RateLimited(Duration::from_secs(65))
+    );
+    assert_eq!(
+        parse_outcome(include_str!("../tests/fixtures/answer_wrong_level.html")),
+        Outcome::WrongLevel
+    );
+}
+
+#[test]
+fn answers_with_spaces() {
+    let day = advent_of_code_2019::find_day(8).unwrap();
+    let mut submissions = Submissions::default();
+    submissions.record(day, 2, "AB CD", &Outcome::Wrong(Hint::None));
+    assert_eq!(submissions.lines, ["day08 pt2 wrong AB CD"]);
+    assert!(submissions.previous(day, 2, "AB CD").is_some());
+    assert!(submissions.previous(day, 2, "AB").is_none());
+    assert!(submissions.previous(day, 2, "AB CD EF").is_none());
+
+    submissions.record(day, 2, "AB CD EF", &Outcome::Correct);
+    assert!(submissions.previous(day, 2, "GH").is_some());
+    assert!(submissions.previous(day, 1, "AB CD").is_none());
+}
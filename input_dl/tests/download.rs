@@ -0,0 +1,202 @@
+mod common;
+
+use common::{input_dl, scratch_dir, MockServer};
+
+#[test]
+fn writes_downloaded_input() {
+    let server = MockServer::start(|_| (200, "1\n2\n3\n".to_owned()));
+    let dir = scratch_dir("writes_downloaded_input");
+
+    let output = input_dl(&dir, &["1", "--no-open", "--base-url", &server.url]);
+    assert!(output.status.success(), "{:?}", output);
+    let input = std::fs::read_to_string(dir.join("data/day01.txt")).unwrap();
+    assert_eq!(input, "1\n2\n3\n");
+
+    let requests = server.requests();
+    let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["/day/1/input", "/day/1"]);
+    for request in &requests {
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.header("cookie"), Some("session=secret"));
+    }
+}
+
+#[test]
+fn skips_cached_input() {
+    let server = MockServer::start(|_| (200, "new".to_owned()));
+    let dir = scratch_dir("skips_cached_input");
+    std::fs::create_dir_all(dir.join("data")).unwrap();
+    std::fs::write(dir.join("data/day02.txt"), "old").unwrap();
+
+    let output = input_dl(&dir, &["2", "--no-open", "--base-url", &server.url]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("data/day02.txt")).unwrap(),
+        "old"
+    );
+    // Neither the input nor the puzzle page is fetched for a cached input
+    assert!(server.requests().is_empty(), "{:?}", server.requests());
+
+    let output = input_dl(
+        &dir,
+        &["2", "--no-open", "--force", "--base-url", &server.url],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("data/day02.txt")).unwrap(),
+        "new"
+    );
+    let requests = server.requests();
+    let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["/day/2/input", "/day/2"]);
+}
+
+#[test]
+fn extracts_examples_and_answers() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/day/1/input" => (200, "12\n".to_owned()),
+        "/day/1" => (200, include_str!("fixtures/day01.html").to_owned()),
+        _ => (404, String::new()),
+    });
+    let dir = scratch_dir("extracts_examples_and_answers");
+    std::fs::create_dir_all(dir.join("data")).unwrap();
+    std::fs::write(dir.join("data/answers.txt"), "day01 pt1 1\nday02 pt1 2\n").unwrap();
+
+    let output = input_dl(&dir, &["1", "--no-open", "--base-url", &server.url]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("data/examples/staged/day01/example1.txt")).unwrap(),
+        "12\n14\n1969\n100756\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("data/answers.txt")).unwrap(),
+        "day01 pt1 3270179\nday01 pt2 4902420\nday02 pt1 2\n"
+    );
+}
+
+#[test]
+fn reports_http_errors() {
+    for (status, message, code) in [
+        (404, "isn't available yet", 5),
+        (400, "session token was rejected", 6),
+        (500, "expected HTTP 200 OK status", 4),
+    ] {
+        let server = MockServer::start(move |_| (status, "error".to_owned()));
+        let dir = scratch_dir(&format!("reports_http_errors_{}", status));
+
+        let output = input_dl(&dir, &["3", "--no-open", "--base-url", &server.url]);
+        assert_eq!(output.status.code(), Some(code));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{}: {}", status, stderr);
+        assert!(!dir.join("data/day03.txt").exists());
+    }
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    let dir = scratch_dir("rejects_invalid_arguments");
+    for args in [
+        &["26"][..],
+        &["x"],
+        &[],
+        &["1", "--base-url"],
+        &["1", "--bogus"],
+        &["1", "--year", "19"],
+        &["1", "--profile", "examples"],
+        &["1", "--profile", "2018"],
+        &["1", "--profile", "../x"],
+        &["1", "--run", "--year", "2018"],
+        &["1", "--hook"],
+    ] {
+        let output = input_dl(&dir, args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("usage:"));
+    }
+}
+
+#[test]
+fn stores_profiles_and_years_separately() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/day/5/input" => (200, "3,0,99\n".to_owned()),
+        "/day/5" => (200, include_str!("fixtures/day01.html").to_owned()),
+        _ => (404, String::new()),
+    });
+    let dir = scratch_dir("stores_profiles_and_years_separately");
+    std::fs::write(dir.join("profiles.txt"), "# accounts\nalt = other\n").unwrap();
+
+    let args = [
+        "5",
+        "--no-open",
+        "--profile",
+        "alt",
+        "--base-url",
+        &server.url,
+    ];
+    let output = input_dl(&dir, &args);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.join("data/alt/day05.txt").exists());
+    assert!(dir.join("data/alt/answers.txt").exists());
+    assert!(dir.join("data/examples/staged/day05/example1.txt").exists());
+    assert!(!dir.join("data/day05.txt").exists());
+    let requests = server.requests();
+    assert_eq!(requests[0].header("cookie"), Some("session=other"));
+
+    let args = [
+        "5",
+        "--no-open",
+        "--year",
+        "2018",
+        "--base-url",
+        &server.url,
+    ];
+    let output = input_dl(&dir, &args);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.join("data/2018/day05.txt").exists());
+    assert!(dir
+        .join("data/2018/examples/staged/day05/example1.txt")
+        .exists());
+    assert_eq!(
+        server.requests()[2].header("cookie"),
+        Some("session=secret")
+    );
+
+    let args = [
+        "5",
+        "--no-open",
+        "--profile",
+        "missing",
+        "--base-url",
+        &server.url,
+    ];
+    let output = input_dl(&dir, &args);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no token for profile"));
+}
+
+#[test]
+fn runs_hook_and_solver() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/day/1/input" => (200, "12\n14\n1969\n100756\n".to_owned()),
+        _ => (200, String::new()),
+    });
+    let dir = scratch_dir("runs_hook_and_solver");
+
+    let hook = "echo {day} {path} > hook.txt";
+    let args = ["1", "--run", "--hook", hook, "--base-url", &server.url];
+    let output = input_dl(&dir, &args);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("34241"), "{}", stdout);
+    assert!(stdout.contains("51316"), "{}", stdout);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("hook.txt")).unwrap(),
+        "1 ./data/day01.txt\n"
+    );
+
+    let args = ["1", "--hook", "exit 3", "--base-url", &server.url];
+    assert_eq!(input_dl(&dir, &args).status.code(), Some(8));
+
+    std::fs::write(dir.join("data/day01.txt"), "not a number").unwrap();
+    let args = ["1", "--run", "--no-open", "--base-url", &server.url];
+    assert_eq!(input_dl(&dir, &args).status.code(), Some(9));
+}
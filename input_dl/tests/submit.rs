@@ -0,0 +1,102 @@
+mod common;
+
+use common::{input_dl, scratch_dir, MockServer};
+
+fn read(dir: &std::path::Path, path: &str) -> String {
+    std::fs::read_to_string(dir.join(path)).unwrap()
+}
+
+#[test]
+fn records_correct_answer() {
+    let server =
+        MockServer::start(|_| (200, include_str!("fixtures/answer_correct.html").to_owned()));
+    let dir = scratch_dir("records_correct_answer");
+
+    let args = ["submit", "1", "1", "3270179", "--base-url", &server.url];
+    let output = input_dl(&dir, &args);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("correct"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/day/1/answer");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=1&answer=3270179");
+    assert_eq!(
+        read(&dir, "data/submissions.txt"),
+        "day01 pt1 correct 3270179\n"
+    );
+    assert_eq!(read(&dir, "data/answers.txt"), "day01 pt1 3270179\n");
+
+    // The part is solved, so nothing is submitted anymore.
+    let args = ["submit", "1", "pt1", "123", "--base-url", &server.url];
+    let output = input_dl(&dir, &args);
+    assert_eq!(output.status.code(), Some(12));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn never_resubmits_wrong_answer() {
+    let server = MockServer::start(|_| {
+        (
+            200,
+            include_str!("fixtures/answer_too_high.html").to_owned(),
+        )
+    });
+    let dir = scratch_dir("never_resubmits_wrong_answer");
+
+    let args = ["submit", "2", "2", "5000000", "--base-url", &server.url];
+    let output = input_dl(&dir, &args);
+    assert_eq!(output.status.code(), Some(10));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("too high"));
+    assert_eq!(
+        read(&dir, "data/submissions.txt"),
+        "day02 pt2 too_high 5000000\n"
+    );
+    assert!(!dir.join("data/answers.txt").exists());
+
+    let output = input_dl(&dir, &args);
+    assert_eq!(output.status.code(), Some(12));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already submitted"));
+    assert_eq!(server.requests().len(), 1);
+
+    let args = ["submit", "2", "2", "4000000", "--base-url", &server.url];
+    input_dl(&dir, &args);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn reports_rate_limit() {
+    let server = MockServer::start(|_| {
+        (
+            200,
+            include_str!("fixtures/answer_rate_limited.html").to_owned(),
+        )
+    });
+    let dir = scratch_dir("reports_rate_limit");
+
+    let args = ["submit", "3", "1", "42", "--base-url", &server.url];
+    let output = input_dl(&dir, &args);
+    assert_eq!(output.status.code(), Some(11));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("rate limited, wait 65s"));
+
+    // Rate limited submissions say nothing about the answer, so it can be retried.
+    input_dl(&dir, &args);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn rejects_invalid_submissions() {
+    let dir = scratch_dir("rejects_invalid_submissions");
+    for args in [
+        &["submit", "1", "3", "42"][..],
+        &["submit", "1", "1"],
+        &["submit", "26", "1", "42"],
+        &["1", "2"],
+    ] {
+        let output = input_dl(&dir, args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("usage:"));
+    }
+}
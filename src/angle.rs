@@ -0,0 +1,141 @@
+//! Exact ordering of integer vectors by their angle, without the rounding of
+//! `atan2`.
+#![allow(dead_code)]
+
+use crate::vec2::Vec2;
+use num::integer::Integer;
+use num::traits::Signed;
+use std::cmp::Ordering;
+
+/// Whether the vector points into the right half, which starts at north and
+/// includes it, or into the left half, which starts at south. The zero vector
+/// comes before both.
+fn half<T: Signed + Copy>(v: &Vec2<T>) -> u8 {
+    if v.x.is_zero() && v.y.is_zero() {
+        0
+    } else if v.x.is_positive() || (v.x.is_zero() && v.y.is_negative()) {
+        1
+    } else {
+        2
+    }
+}
+
+/// Compares the angles of the vectors clockwise from north, with `y` pointing
+/// down like `Direction` does, so north is `(0, -1)` and comes first. Vectors
+/// pointing the same way are equal, and the zero vector comes before all
+/// others. The cross product of the vectors has to fit in `T`.
+pub fn cmp_clockwise<T: Signed + Copy>(a: &Vec2<T>, b: &Vec2<T>) -> Ordering {
+    half(a).cmp(&half(b)).then_with(|| {
+        // Within a half the angle between them is less than half a turn, so
+        // the sign of the cross product tells which one comes first
+        let cross = a.cross(*b);
+        if cross.is_positive() {
+            Ordering::Less
+        } else if cross.is_negative() {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    })
+}
+
+/// Orders a vector by its angle using `cmp_clockwise`, so vectors pointing the
+/// same way are equal even if their lengths differ.
+#[derive(Debug, Clone, Copy)]
+pub struct ByAngle<T>(pub Vec2<T>);
+
+impl<T: Signed + Copy> PartialEq for ByAngle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<T: Signed + Copy> Eq for ByAngle<T> {}
+
+impl<T: Signed + Copy> PartialOrd for ByAngle<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Signed + Copy> Ord for ByAngle<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_clockwise(&self.0, &other.0)
+    }
+}
+
+/// The direction of a vector, stored as the vector reduced by the GCD of its
+/// coordinates. Vectors pointing the same way get the same key, so it can be
+/// hashed as well as ordered by angle like `ByAngle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectionKey<T>(Vec2<T>);
+
+impl<T: Integer + Signed + Copy> DirectionKey<T> {
+    /// The key of the direction of `v`, or `None` for the zero vector, which
+    /// has no direction.
+    pub fn new(v: Vec2<T>) -> Option<Self> {
+        if v.x.is_zero() && v.y.is_zero() {
+            None
+        } else {
+            Some(DirectionKey(v.normalized()))
+        }
+    }
+
+    /// The shortest integer vector in this direction.
+    pub fn step(self) -> Vec2<T> {
+        self.0
+    }
+}
+
+impl<T: Integer + Signed + Copy> PartialOrd for DirectionKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer + Signed + Copy> Ord for DirectionKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Keys with the same angle are the same vector, so this is consistent
+        // with `Eq`
+        cmp_clockwise(&self.0, &other.0)
+    }
+}
+
+#[test]
+fn angles() {
+    use crate::vec2::Vec2i;
+    let clockwise = [
+        Vec2i::new(0, -3),
+        Vec2i::new(1, -1000),
+        Vec2i::new(1, -1),
+        Vec2i::new(5, 0),
+        Vec2i::new(2, 1),
+        Vec2i::new(0, 1),
+        Vec2i::new(-1, 1000),
+        Vec2i::new(-1, 0),
+        Vec2i::new(-1, -1),
+        Vec2i::new(-1, -1000),
+    ];
+    let mut shuffled = clockwise.to_vec();
+    shuffled.reverse();
+    shuffled.swap(2, 7);
+    shuffled.sort_unstable_by(cmp_clockwise);
+    assert_eq!(shuffled, clockwise);
+    assert!(ByAngle(Vec2i::new(0, 0)) < ByAngle(Vec2i::new(0, -1)));
+    assert_eq!(ByAngle(Vec2i::new(2, -4)), ByAngle(Vec2i::new(1, -2)));
+    assert_ne!(ByAngle(Vec2i::new(1, -2)), ByAngle(Vec2i::new(-1, 2)));
+
+    // Angles that are too close together for `f32`
+    let a = Vec2::<i64>::new(100_000, -99_999);
+    let b = Vec2::<i64>::new(99_999, -99_998);
+    assert_eq!(
+        (a.x as f32).atan2(-a.y as f32),
+        (b.x as f32).atan2(-b.y as f32)
+    );
+    assert_eq!(cmp_clockwise(&a, &b), Ordering::Less);
+
+    let key = DirectionKey::new(Vec2i::new(-6, 4)).unwrap();
+    assert_eq!(key.step(), Vec2i::new(-3, 2));
+    assert_eq!(DirectionKey::new(Vec2i::new(-3, 2)), Some(key));
+    assert_eq!(DirectionKey::new(Vec2i::new(0, 0)), None);
+    assert!(DirectionKey::new(Vec2i::new(0, -7)).unwrap() < key);
+}
@@ -0,0 +1,184 @@
+//! Finding where a sequence of states, generated by repeatedly applying a step
+//! function to an initial state, starts repeating itself.
+#![allow(dead_code)]
+
+use crate::HashMap;
+use num::Integer;
+use std::hash::Hash;
+
+/// The states from index `start` onwards repeat every `length` steps, where
+/// the initial state has index 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The index of the first state that is equal to the state at `index`,
+    /// which is useful to find a state far in the future.
+    pub fn first_index_of(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare, which only keeps two states around. Never
+/// returns if the states don't repeat.
+pub fn floyd<S, F>(init: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // The hare moves twice as fast, so they meet somewhere in the cycle
+    let mut tortoise = step(&init);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    // The distance between them is now a multiple of the length, so moving at
+    // the same speed they meet at the start
+    let mut start = 0;
+    tortoise = init;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, which keeps two states around like `floyd`, but needs
+/// fewer steps. Never returns if the states don't repeat.
+pub fn brent<S, F>(init: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // The tortoise teleports to the hare at every power of two, until the
+    // hare runs into it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = init.clone();
+    let mut hare = step(&init);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare a cycle ahead, they meet at the start
+    let mut start = 0;
+    tortoise = init.clone();
+    hare = init;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Remembers every state, so it only needs to step until the first repeat.
+/// Returns the cycle along with the first state that repeats. Never returns
+/// if the states don't repeat.
+pub fn hashed<S, F>(init: S, mut step: F) -> (Cycle, S)
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = init;
+    for index in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return (cycle, state);
+        }
+        let next = step(&state);
+        seen.insert(state, index);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// The cycle of a state made of independent components, such as separate
+/// axes, given the cycle of each component. It starts once all components
+/// are in their cycle, and its length is the LCM of their lengths.
+pub fn combine<I>(cycles: I) -> Cycle
+where
+    I: IntoIterator<Item = Cycle>,
+{
+    cycles.into_iter().fold(
+        Cycle {
+            start: 0,
+            length: 1,
+        },
+        |combined, cycle| Cycle {
+            start: combined.start.max(cycle.start),
+            length: combined.length.lcm(&cycle.length),
+        },
+    )
+}
+
+#[test]
+fn cycles() {
+    // 0, 1, 2, 3, 4, 2, 3, 4, ...
+    let step = |&x: &u32| if x == 4 { 2 } else { x + 1 };
+    let expected = Cycle {
+        start: 2,
+        length: 3,
+    };
+    assert_eq!(floyd(0, step), expected);
+    assert_eq!(brent(0, step), expected);
+    assert_eq!(hashed(0, step), (expected, 2));
+    assert_eq!(expected.first_index_of(1), 1);
+    assert_eq!(expected.first_index_of(1_000_000), 4);
+
+    // A longer sequence that doesn't cycle back to its start
+    let step = |&x: &u64| (x * x + 1) % 1019;
+    let (expected, _) = hashed(3, step);
+    assert_ne!(expected.start, 0);
+    assert_eq!(floyd(3, step), expected);
+    assert_eq!(brent(3, step), expected);
+
+    let combined = combine(vec![
+        Cycle {
+            start: 0,
+            length: 4,
+        },
+        Cycle {
+            start: 2,
+            length: 6,
+        },
+    ]);
+    assert_eq!(
+        combined,
+        Cycle {
+            start: 2,
+            length: 12
+        }
+    );
+}
@@ -51,7 +51,7 @@ fn pt1(wires: (Wire, Wire)) -> Result<i64> {
     let wire1_positions: HashSet<Vec2> = HashSet::from_iter(iter_wire(wires.0));
     iter_wire(wires.1)
         .filter(|pos| wire1_positions.contains(pos))
-        .map(|pos| pos.x.abs() + pos.y.abs())
+        .map(|pos| pos.manhattan_distance(Vec2::zero()))
         .min()
         .ok_or(AoCError::NoSolution)
 }
@@ -104,21 +104,6 @@ fn parse(s: &str) -> IResult<&str, (Wire, Wire)> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use ::test::{black_box, Bencher};
-
-    #[bench]
-    fn day03_pt1(b: &mut Bencher) {
-        let input = std::fs::read_to_string("./data/day03.txt").unwrap();
-        let input = input.trim();
-        b.iter(|| pt1(parse(black_box(input)).unwrap().1));
-    }
-
-    #[bench]
-    fn day03_pt2(b: &mut Bencher) {
-        let input = std::fs::read_to_string("./data/day03.txt").unwrap();
-        let input = input.trim();
-        b.iter(|| pt2(parse(black_box(input)).unwrap().1));
-    }
 
     #[test]
     fn day03() -> Result<()> {
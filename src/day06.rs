@@ -1,6 +1,6 @@
 module!(pt1: parse, pt2: parse);
 
-use crate::graph::dfs;
+use crate::graph::LcaTree;
 use crate::HashMap;
 use std::collections::hash_map::Entry;
 
@@ -12,30 +12,10 @@ struct Orbit<'s> {
 
 #[derive(Debug)]
 struct Body<'s> {
-    name: &'s str,
     parent: Option<&'s str>,
     children: Vec<&'s str>,
 }
 
-struct ParentIter<'h, 's: 'h>(&'h HashMap<&'s str, Body<'s>>, Option<&'s str>);
-impl<'h, 's: 'h> Iterator for ParentIter<'h, 's> {
-    type Item = &'h Body<'s>;
-    fn next(&mut self) -> Option<&'h Body<'s>> {
-        if let Some(body) = self.0.get(&self.1?) {
-            self.1 = body.parent;
-            Some(body)
-        } else {
-            None
-        }
-    }
-}
-fn parent_iter<'h, 's: 'h>(
-    map: &'h HashMap<&'s str, Body<'s>>,
-    start: &'s str,
-) -> ParentIter<'h, 's> {
-    ParentIter(map, Some(start))
-}
-
 fn create_bodies<'s>(orbits: &[Orbit<'s>]) -> Result<HashMap<&'s str, Body<'s>>> {
     let mut bodies: HashMap<&str, Body> = HashMap::with_capacity(orbits.len());
     for orbit in orbits {
@@ -45,7 +25,6 @@ fn create_bodies<'s>(orbits: &[Orbit<'s>]) -> Result<HashMap<&'s str, Body<'s>>>
             }
             Entry::Vacant(slot) => {
                 slot.insert(Body {
-                    name: orbit.target,
                     parent: None,
                     children: vec![orbit.object],
                 });
@@ -64,7 +43,6 @@ fn create_bodies<'s>(orbits: &[Orbit<'s>]) -> Result<HashMap<&'s str, Body<'s>>>
             }
             Entry::Vacant(slot) => {
                 slot.insert(Body {
-                    name: orbit.object,
                     parent: Some(orbit.target),
                     children: Vec::new(),
                 });
@@ -74,36 +52,31 @@ fn create_bodies<'s>(orbits: &[Orbit<'s>]) -> Result<HashMap<&'s str, Body<'s>>>
     Ok(bodies)
 }
 
+/// The tree of bodies orbiting the center of mass.
+fn orbit_tree<'s>(orbits: &[Orbit<'s>]) -> Result<LcaTree<&'s str>> {
+    let bodies = create_bodies(orbits)?;
+    Ok(LcaTree::new("COM", |name| {
+        bodies
+            .get(name)
+            .into_iter()
+            .flat_map(|body| body.children.iter().cloned())
+    }))
+}
+
 fn pt1(orbits: Vec<Orbit>) -> Result<usize> {
-    let bodies = create_bodies(&orbits)?;
-    let mut total = 0;
-    dfs(("COM", 0), |(name, current_depth)| {
-        total += current_depth;
-        let body = &bodies[name];
-        body.children
-            .iter()
-            .map(move |&child| (child, current_depth + 1))
-    });
-    Ok(total)
+    let tree = orbit_tree(&orbits)?;
+    Ok(tree.depths().map(|(_, depth)| depth).sum())
 }
 
 fn pt2(orbits: Vec<Orbit>) -> Result<usize> {
-    let bodies = create_bodies(&orbits)?;
-    let visited = parent_iter(&bodies, "YOU")
-        .enumerate()
-        .map(|(dist, body)| (body.name, dist))
-        .collect::<HashMap<_, _>>();
-
-    parent_iter(&bodies, "SAN")
-        .enumerate()
-        .filter_map(|(dist2, body)| visited.get(&body.name).map(|dist1| dist1 + dist2))
-        .next()
-        // The distance should not include YOU and SAN themselves
+    let tree = orbit_tree(&orbits)?;
+    tree.distance(&"YOU", &"SAN")
+        // The distance is between the bodies YOU and SAN orbit
         .map(|x| x - 2)
         .ok_or(AoCError::NoSolution)
 }
 
-fn parse(s: &str) -> IResult<&str, Vec<Orbit>> {
+fn parse(s: &str) -> IResult<&str, Vec<Orbit<'_>>> {
     use parsers::*;
     let orbit = map(
         pair(terminated(alphanumeric1, char(')')), alphanumeric1),
@@ -111,45 +84,3 @@ fn parse(s: &str) -> IResult<&str, Vec<Orbit>> {
     );
     separated_list1(line_ending, orbit)(s)
 }
-
-#[test]
-fn day06() -> Result<()> {
-    use crate::module::ToModuleResult;
-    let input = parse(
-        "\
-COM)B
-B)C
-C)D
-D)E
-E)F
-B)G
-G)H
-D)I
-E)J
-J)K
-K)L",
-    )
-    .to_module_result()?;
-    assert_eq!(pt1(input)?, 42);
-
-    let input = parse(
-        "\
-COM)B
-B)C
-C)D
-D)E
-E)F
-B)G
-G)H
-D)I
-E)J
-J)K
-K)L
-K)YOU
-I)SAN",
-    )
-    .to_module_result()?;
-    assert_eq!(pt2(input)?, 4);
-
-    Ok(())
-}
@@ -7,7 +7,7 @@ use std::collections::VecDeque;
 module!(pt1: parse_intcode, pt2: parse_intcode);
 
 fn pt1(memory: Vec<i64>) -> Result<String> {
-    let mut max = std::i64::MIN;
+    let mut max = i64::MIN;
     let mut max_phases = Vec::new();
     for phases in (0..=4).permutations(5) {
         let mut last_output = 0;
@@ -35,7 +35,7 @@ fn pt1(memory: Vec<i64>) -> Result<String> {
 }
 
 fn pt2(memory: Vec<i64>) -> Result<String> {
-    let mut max = std::i64::MIN;
+    let mut max = i64::MIN;
     let mut max_phases = Vec::new();
 
     for phases in (5..=9).permutations(5) {
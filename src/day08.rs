@@ -1,28 +1,17 @@
 module!(pt1: parse, pt2: parse);
 use crate::mat2::Mat2;
+use crate::ocr;
 use crate::vec2::Vec2us;
-use itertools::Itertools;
 
 fn parse_image_layers(size: Vec2us, data: &[u8]) -> Result<Vec<Mat2<u8>>> {
-    if data.len() % (size.x * size.y) != 0 {
+    if !data.len().is_multiple_of(size.x * size.y) {
         return Err(AoCError::Logic(
             "image data length not a multiple of width * size",
         ));
     }
     Ok(data
-        .iter()
-        .cloned()
         .chunks(size.x * size.y)
-        .into_iter()
-        .map(|chunk| {
-            let mut image = Mat2::new(0, size);
-            for (y, row) in chunk.chunks(size.x).into_iter().enumerate() {
-                for (x, value) in row.enumerate() {
-                    image[x][y] = value;
-                }
-            }
-            image
-        })
+        .map(|layer| Mat2::from_fn(size, |pos| layer[pos.y * size.x + pos.x]))
         .collect())
 }
 
@@ -43,8 +32,10 @@ fn pt1((size, layers): (Vec2us, Vec<u8>)) -> Result<usize> {
     Ok(ones * twos)
 }
 
-fn pt2((size, layers): (Vec2us, Vec<u8>)) -> Result<String> {
-    let mut layers = parse_image_layers(size, &layers)?;
+/// Stacks the layers, where the pixels of the first layers are on top and 2
+/// is transparent. Returns which pixels are white.
+fn decode_image(size: Vec2us, data: &[u8]) -> Result<Mat2<bool>> {
+    let mut layers = parse_image_layers(size, data)?;
     let mut output = layers.remove(0);
 
     for layer in layers {
@@ -55,23 +46,20 @@ fn pt2((size, layers): (Vec2us, Vec<u8>)) -> Result<String> {
         }
     }
 
-    let mut res = String::with_capacity((size.x + size.x / 5 - 1) * size.y + size.y - 1);
-    for y in 0..size.y {
-        for x in 0..size.x {
-            if x != 0 && x % 5 == 0 {
-                res.push(' ');
-            }
-            match output[Vec2us::new(x, y)] {
-                0 => res.push(' '),
-                1 => res.push('█'),
-                _ => return Err(AoCError::NoSolution),
-            }
-        }
-        if y != size.y - 1 {
-            res.push('\n');
-        }
+    let mut image = Mat2::new(false, size);
+    for (pos, value) in output.iter() {
+        image[pos] = match value {
+            0 => false,
+            1 => true,
+            _ => return Err(AoCError::NoSolution),
+        };
     }
-    Ok(res)
+    Ok(image)
+}
+
+fn pt2((size, layers): (Vec2us, Vec<u8>)) -> Result<String> {
+    let image = decode_image(size, &layers)?;
+    ocr::read_letters(&image, |&white| white)
 }
 
 fn parse(s: &str) -> IResult<&str, (Vec2us, Vec<u8>)> {
@@ -84,6 +72,10 @@ fn parse(s: &str) -> IResult<&str, (Vec2us, Vec<u8>)> {
 
 #[test]
 fn day08() -> Result<()> {
-    assert_eq!(pt2((Vec2us::new(2, 2), (parse("0222112222120000")?.1).1))?, " █\n█ ");
+    let image = decode_image(Vec2us::new(2, 2), &(parse("0222112222120000")?.1).1)?;
+    assert_eq!(
+        image.render(|&white| if white { '█' } else { ' ' }),
+        " █\n█ \n"
+    );
     Ok(())
 }
@@ -1,37 +1,12 @@
 module!(pt1: parse, pt2: parse);
 
+use crate::angle::DirectionKey;
 use crate::mat2::Mat2;
-use crate::vec2::Vec2us;
 use crate::HashSet;
-use num::integer::Integer;
-use std::convert::Into;
+use std::collections::BTreeMap;
 
 type Vec2 = crate::vec2::Vec2i;
 
-struct CloserToCenterIter {
-    pos: Vec2,
-    offset: Vec2,
-}
-impl Iterator for CloserToCenterIter {
-    type Item = Vec2;
-    fn next(&mut self) -> Option<Vec2> {
-        if self.pos == (0, 0).into() {
-            return None;
-        }
-        let prev = self.pos;
-        self.pos -= self.offset;
-        Some(prev)
-    }
-}
-
-fn closer_to_center(pos: Vec2) -> CloserToCenterIter {
-    let offset = pos / pos.x.abs().gcd(&pos.y.abs()).max(1);
-    CloserToCenterIter {
-        pos: pos - offset,
-        offset,
-    }
-}
-
 fn asteroid_positions<'a>(grid: &'a Mat2<bool>) -> impl Iterator<Item = Vec2> + Clone + 'a {
     grid.iter()
         .filter(|&(_, &is_asteroid)| is_asteroid)
@@ -42,45 +17,48 @@ fn find_ideal_spot(asteroids: &HashSet<Vec2>) -> (Vec2, usize) {
     asteroids
         .iter()
         .map(|&asteroid1| {
-            (
-                asteroid1,
-                asteroids
-                    .iter()
-                    .filter(|&&asteroid2| {
-                        closer_to_center(asteroid2 - asteroid1)
-                            .all(|pos| !asteroids.contains(&(pos + asteroid1)))
-                    })
-                    .count()
-                    // subtract 1, because asteroid1 itself is also visible
-                    - 1,
-            )
+            // Only the closest asteroid in every direction is visible
+            let directions: HashSet<_> = asteroids
+                .iter()
+                .filter_map(|&asteroid2| DirectionKey::new(asteroid2 - asteroid1))
+                .collect();
+            (asteroid1, directions.len())
         })
         .max_by_key(|(_, visible_count)| *visible_count)
         .unwrap()
 }
 
-fn quantized_angle(pos: Vec2) -> u32 {
-    let quantized = ((pos.x as f64).atan2(-pos.y as f64) * 100_000_000f64).round() as i32;
-    quantized as u32
-}
-
 fn vaporization_order(mut asteroids: HashSet<Vec2>) -> (Vec2, Vec<Vec2>) {
     let laser = find_ideal_spot(&asteroids).0;
     asteroids.remove(&laser);
 
-    let mut vaporization_order: Vec<_> =
-        asteroids.iter().map(|&asteroid| asteroid - laser).collect();
-    vaporization_order.sort_unstable_by_key(|&asteroid| {
-        let closer_to_center_count = closer_to_center(asteroid)
-            .filter(|&pos| asteroids.contains(&(pos + laser)))
-            .count();
-        (closer_to_center_count, quantized_angle(asteroid))
-    });
-    for asteroid in &mut vaporization_order {
-        *asteroid += laser;
+    let mut by_direction: BTreeMap<DirectionKey<i32>, Vec<Vec2>> = BTreeMap::new();
+    for &asteroid in &asteroids {
+        let offset = asteroid - laser;
+        by_direction
+            .entry(DirectionKey::new(offset).unwrap())
+            .or_default()
+            .push(offset);
+    }
+
+    // Every rotation the laser vaporizes the closest remaining asteroid in
+    // each direction, going clockwise from up
+    let mut vaporization_order = Vec::with_capacity(asteroids.len());
+    for (key, mut in_direction) in by_direction {
+        in_direction.sort_unstable_by_key(|offset| offset.manhattan_distance(Vec2::default()));
+        for (rotation, offset) in in_direction.into_iter().enumerate() {
+            vaporization_order.push((rotation, key, offset + laser));
+        }
     }
+    vaporization_order.sort_unstable_by_key(|&(rotation, key, _)| (rotation, key));
 
-    (laser, vaporization_order)
+    (
+        laser,
+        vaporization_order
+            .into_iter()
+            .map(|(_, _, asteroid)| asteroid)
+            .collect(),
+    )
 }
 
 fn pt1(grid: Mat2<bool>) -> usize {
@@ -95,35 +73,19 @@ fn pt2(grid: Mat2<bool>) -> Result<i32> {
         .ok_or(AoCError::NoSolution)
 }
 
-fn parse(s: &str) -> IResult<&str, Mat2<bool>> {
-    use parsers::*;
-    let line = many1(map(one_of(".#"), |c| c == '#'));
-    let grid = separated_list1(line_ending, line);
-    map_res(grid, |lines| {
-        let height = lines.len();
-        if height == 0 {
-            return Err(AoCError::NomParse("empty input".to_owned()));
-        }
-        let width = lines[0].len();
-        if lines[1..].iter().any(|row| row.len() != width) {
-            return Err(AoCError::NomParse("inconsistent row width".to_owned()));
-        }
-        let mut grid = Mat2::new(false, Vec2us::new(width, height));
-        for (y, line) in lines.iter().enumerate() {
-            for (x, &value) in line.iter().enumerate() {
-                grid[Vec2us::new(x, y)] = value;
-            }
-        }
-        Ok(grid)
-    })(s)
+fn parse(s: &str) -> Result<Mat2<bool>> {
+    Mat2::from_text(s, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(AoCError::IncorrectInput("expected '#' or '.'")),
+    })
 }
 
 #[test]
 fn day10() -> Result<()> {
     let vape_order = vaporization_order(
-        asteroid_positions(
-            &parse(
-                "\
+        asteroid_positions(&parse(
+            "\
 .#..##.###...#######
 ##.############..##.
 .#.######.########.#
@@ -144,9 +106,7 @@ fn day10() -> Result<()> {
 .#.#.###########.###
 #.#.#.#####.####.###
 ###.##.####.##.#..##",
-            )?
-            .1,
-        )
+        )?)
         .collect(),
     )
     .1;
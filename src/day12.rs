@@ -1,7 +1,6 @@
 module!(pt1: parse, pt2: parse);
 
-use crate::HashSet;
-use num::{Integer, Signed};
+use crate::cycle::{self, Cycle};
 use std::convert::Into;
 
 type Vec3 = crate::vec3::Vec3i;
@@ -14,13 +13,11 @@ struct CelestialBody {
 
 impl CelestialBody {
     fn potential_energy(&self) -> i32 {
-        let p = self.position.abs();
-        p.x + p.y + p.z
+        self.position.manhattan_distance(Vec3::default())
     }
 
     fn kinetic_energy(&self) -> i32 {
-        let v = self.velocity.abs();
-        v.x + v.y + v.z
+        self.velocity.manhattan_distance(Vec3::default())
     }
 }
 
@@ -30,9 +27,9 @@ fn move_bodies(bodies: &mut [CelestialBody]) {
             let pa = bodies[a].position;
             let pb = bodies[b].position;
             let mut v = pb - pa;
-            v.x = v.x.min(1).max(-1);
-            v.y = v.y.min(1).max(-1);
-            v.z = v.z.min(1).max(-1);
+            v.x = v.x.clamp(-1, 1);
+            v.y = v.y.clamp(-1, 1);
+            v.z = v.z.clamp(-1, 1);
             bodies[a].velocity += v;
             bodies[b].velocity -= v;
         }
@@ -62,37 +59,33 @@ struct BodyPart {
     velocity: i32,
 }
 
-fn calculate_cycle_time(initial_parts: [BodyPart; 4]) -> Result<u64> {
-    let mut parts = initial_parts;
-    let mut map = HashSet::new();
-    let mut iteration = 0;
-    loop {
-        if !map.insert(parts) {
-            break if parts != initial_parts {
-                Err(AoCError::IncorrectInput(
-                    "cycle doesn't start from the initial position",
-                ))
-            } else {
-                Ok(iteration)
-            };
-        }
-        iteration += 1;
-
-        // Update velocity
-        for i in 0..3 {
-            let pa = parts[i].position;
-            for j in i + 1..4 {
-                let pb = parts[j].position;
-                let v = (pb - pa).min(1).max(-1);
-                parts[i].velocity += v;
-                parts[j].velocity -= v;
-            }
-        }
-        // Update position
-        for part in &mut parts {
-            part.position += part.velocity;
+fn step_parts(parts: &[BodyPart; 4]) -> [BodyPart; 4] {
+    let mut parts = *parts;
+    // Update velocity
+    for i in 0..3 {
+        let pa = parts[i].position;
+        for j in i + 1..4 {
+            let pb = parts[j].position;
+            let v = (pb - pa).clamp(-1, 1);
+            parts[i].velocity += v;
+            parts[j].velocity -= v;
         }
     }
+    // Update position
+    for part in &mut parts {
+        part.position += part.velocity;
+    }
+    parts
+}
+
+fn calculate_cycle(initial_parts: [BodyPart; 4]) -> Result<Cycle> {
+    let cycle = cycle::brent(initial_parts, step_parts);
+    if cycle.start != 0 {
+        return Err(AoCError::IncorrectInput(
+            "cycle doesn't start from the initial position",
+        ));
+    }
+    Ok(cycle)
 }
 
 fn pt2(bodies: Vec<CelestialBody>) -> Result<u64> {
@@ -113,12 +106,12 @@ fn pt2(bodies: Vec<CelestialBody>) -> Result<u64> {
         parts[2][i].velocity = body.velocity.z;
     }
 
-    let cycle_times = [
-        calculate_cycle_time(parts[0])?,
-        calculate_cycle_time(parts[1])?,
-        calculate_cycle_time(parts[2])?,
+    let cycles = [
+        calculate_cycle(parts[0])?,
+        calculate_cycle(parts[1])?,
+        calculate_cycle(parts[2])?,
     ];
-    Ok(cycle_times[0].lcm(&cycle_times[1]).lcm(&cycle_times[2]))
+    Ok(cycle::combine(cycles.iter().cloned()).length as u64)
 }
 
 fn parse(s: &str) -> IResult<&str, Vec<CelestialBody>> {
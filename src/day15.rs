@@ -1,20 +1,19 @@
 use crate::direction::{Direction, MoveInDirection};
-use crate::graph::{astar_once, bfs_meta};
+use crate::graph::{bfs_layers, bfs_path};
 use crate::intcode::{
     sparse_memory, util::parse_intcode, Error as icError, IoOperation, Value, VM,
 };
-use crate::vec2::AabbIteratorEx;
-use crate::HashMap;
+use crate::sparse_grid::SparseGrid;
 use arrayvec::ArrayVec;
 use std::convert::{TryFrom, TryInto};
 
 type Vec2 = crate::vec2::Vec2<Value>;
-type Map = HashMap<Vec2, Tile>;
+type Map = SparseGrid<Tile>;
 
 module!(pt1: parse_intcode, pt2: parse_intcode);
 
 fn map_area(memory: &[Value]) -> Result<(Map, Vec2)> {
-    let mut map = HashMap::new();
+    let mut map = SparseGrid::new();
     let mut stack: Vec<Direction> = Vec::new();
     let mut position = Vec2::default();
 
@@ -24,16 +23,16 @@ fn map_area(memory: &[Value]) -> Result<(Map, Vec2)> {
     vm.run_all_async(|io| {
         match io {
             IoOperation::Read(value) => {
-                let direction = if !map.contains_key(&Vec2::new(position.x, position.y - 1)) {
+                let direction = if !map.contains(Vec2::new(position.x, position.y - 1)) {
                     stack.push(Direction::North);
                     Direction::North
-                } else if !map.contains_key(&Vec2::new(position.x, position.y + 1)) {
+                } else if !map.contains(Vec2::new(position.x, position.y + 1)) {
                     stack.push(Direction::South);
                     Direction::South
-                } else if !map.contains_key(&Vec2::new(position.x - 1, position.y)) {
+                } else if !map.contains(Vec2::new(position.x - 1, position.y)) {
                     stack.push(Direction::West);
                     Direction::West
-                } else if !map.contains_key(&Vec2::new(position.x + 1, position.y)) {
+                } else if !map.contains(Vec2::new(position.x + 1, position.y)) {
                     stack.push(Direction::East);
                     Direction::East
                 } else if let Some(prev_direction) = stack.pop() {
@@ -89,75 +88,40 @@ fn map_area(memory: &[Value]) -> Result<(Map, Vec2)> {
 
 #[allow(unused)]
 fn visualize_map(map: &Map) -> String {
-    let (min, max) = map.keys().cloned().aabb().unwrap();
-    let mut s = String::new();
-    for y in min.y..=max.y {
-        for x in min.x..=max.x {
-            s.push(match map.get(&Vec2::new(x, y)) {
-                Some(Tile::Open) => '.',
-                Some(Tile::OxygenSystem) => 'O',
-                Some(Tile::Wall) => '#',
-                None => ' ',
-            });
-        }
-        s.push('\n');
-    }
+    let mut s = map.render(|tile| match tile {
+        Some(Tile::Open) => '.',
+        Some(Tile::OxygenSystem) => 'O',
+        Some(Tile::Wall) => '#',
+        None => ' ',
+    });
     s.pop();
     s
 }
 
 fn neighbors(map: &Map, pos: Vec2) -> ArrayVec<Vec2, 4> {
-    let mut neighbors = ArrayVec::<Vec2, 4>::new();
-    neighbors.push(Vec2::new(pos.x, pos.y - 1));
-    neighbors.push(Vec2::new(pos.x, pos.y + 1));
-    neighbors.push(Vec2::new(pos.x - 1, pos.y));
-    neighbors.push(Vec2::new(pos.x + 1, pos.y));
-    for i in (0..4).rev() {
-        match map.get(&neighbors[i]) {
-            None | Some(Tile::Wall) => {
-                neighbors.swap_remove(i);
-            }
-            Some(Tile::Open) | Some(Tile::OxygenSystem) => {}
-        }
-    }
-    neighbors
+    map.neighbors(pos)
+        .filter(|(_, _, &tile)| tile != Tile::Wall)
+        .map(|(_, pos, _)| pos)
+        .collect()
 }
 
 fn pt1(memory: Vec<Value>) -> Result<usize> {
     let (map, oxygen_position) = map_area(&memory)?;
 
-    Ok(astar_once(
+    let path = bfs_path(
         Vec2::new(0, 0),
-        |pos| neighbors(&map, *pos).into_iter().map(|p| (p, 1)),
-        |pos| {
-            let delta = pos.delta(oxygen_position);
-            (delta.x + delta.y) as usize
-        },
+        |pos| neighbors(&map, *pos),
         |pos| *pos == oxygen_position,
     )
-    .unwrap()
-    .last()
-    .unwrap()
-    .1)
+    .ok_or(AoCError::NoSolution)?;
+    Ok(path.len() - 1)
 }
 
 fn pt2(memory: Vec<Value>) -> Result<usize> {
     let (map, oxygen_position) = map_area(&memory)?;
-    let mut max_dist = 0;
-    bfs_meta(
-        (oxygen_position, 0),
-        |&pos, &dist| {
-            neighbors(&map, pos)
-                .into_iter()
-                .map(move |pos| (pos, dist + 1))
-        },
-        |_, &dist| {
-            max_dist = dist;
-            false
-        },
-    );
-
-    Ok(max_dist)
+    // The oxygen spreads one layer per minute
+    let layers = bfs_layers(oxygen_position, |pos| neighbors(&map, *pos)).count();
+    Ok(layers - 1)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1,7 +1,7 @@
 #![allow(clippy::unreadable_literal, clippy::identity_op)]
-use std::iter::repeat;
+use std::iter::repeat_n;
 
-module!(pt1: parse, pt2: parse);
+module!(pt1: parse, pt2: parse; time_budget: std::time::Duration::from_secs(30));
 
 /// Calculates the binomial coefficients modulo 10.
 /// Each entry is nCr(n, m) % 10 where:
@@ -33,11 +33,10 @@ fn calculate_binomial_coefficients_mod_10(width: usize, depth: usize) -> Vec<u8>
 
 #[inline]
 fn fft_pattern(idx: usize) -> impl Iterator<Item = i32> + Clone {
-    repeat(0)
-        .take(idx + 1)
-        .chain(repeat(1).take(idx + 1))
-        .chain(repeat(0).take(idx + 1))
-        .chain(repeat(-1).take(idx + 1))
+    repeat_n(0, idx + 1)
+        .chain(repeat_n(1, idx + 1))
+        .chain(repeat_n(0, idx + 1))
+        .chain(repeat_n(-1, idx + 1))
         .cycle()
         .skip(1)
 }
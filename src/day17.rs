@@ -5,9 +5,9 @@ use crate::intcode::{
     util::{parse_intcode, reading_not_supported},
     Error as icError, Value, VM,
 };
-use crate::HashSet;
+use crate::sparse_grid::SparseGrid;
 use itertools::Itertools;
-type Vec2 = crate::vec2::Vec2<usize>;
+type Vec2 = crate::vec2::Vec2<i64>;
 
 module!(pt1: parse_intcode, pt2: parse_intcode);
 
@@ -15,13 +15,13 @@ fn get_scaffolding_image(memory: Vec<Value>) -> Result<Image> {
     use num::ToPrimitive;
     let mut vm = VM::new(growing_memory(memory));
 
-    let mut walls = HashSet::new();
+    let mut walls = SparseGrid::new();
     let mut pos = Vec2::new(0, 0);
     let mut robot = None;
     vm.run_all(reading_not_supported, |c| {
         match c.to_u8() {
             Some(b'#') => {
-                walls.insert(pos);
+                walls.insert(pos, ());
             }
             Some(c @ b'^') | Some(c @ b'v') | Some(c @ b'<') | Some(c @ b'>') => {
                 if let Some((prev_pos, _)) = robot {
@@ -30,7 +30,7 @@ fn get_scaffolding_image(memory: Vec<Value>) -> Result<Image> {
                         prev_pos, pos
                     )));
                 }
-                walls.insert(pos);
+                walls.insert(pos, ());
                 robot = Some((
                     pos,
                     match c {
@@ -69,19 +69,19 @@ fn directions_with_scaffold<'img>(
     pos: Vec2,
     image: &'img Image,
 ) -> impl Iterator<Item = (Direction, Vec2)> + 'img {
-    Direction::each().filter_map(move |dir| {
-        pos.step_in_direction_checked(dir)
-            .filter(|next_pos| image.walls.contains(&next_pos))
-            .map(|next_pos| (dir, next_pos))
-    })
+    image
+        .walls
+        .neighbors(pos)
+        .map(|(dir, next_pos, _)| (dir, next_pos))
 }
 
-fn pt1(memory: Vec<Value>) -> Result<usize> {
+fn pt1(memory: Vec<Value>) -> Result<i64> {
     let image = get_scaffolding_image(memory)?;
     Ok(image
         .walls
         .iter()
-        .filter(|&&pos| directions_with_scaffold(pos, &image).count() == 4)
+        .map(|(pos, _)| pos)
+        .filter(|&pos| directions_with_scaffold(pos, &image).count() == 4)
         .map(|pos| pos.x * pos.y)
         .sum())
 }
@@ -91,7 +91,7 @@ fn pt2(mut memory: Vec<Value>) -> Result<Value> {
     let (main, funcs) = create_path_and_program(&image)?.ok_or(AoCError::NoSolution)?;
 
     let mut input = String::new();
-    input.extend(Iterator::intersperse(
+    input.extend(Itertools::intersperse(
         main.into_iter().map(|idx| match idx {
             0 => 'A',
             1 => 'B',
@@ -173,7 +173,7 @@ fn create_path_and_program(image: &Image) -> Result<Option<Program>> {
 }
 
 fn create_path(image: &Image) -> Result<Vec<Command>> {
-    let mut walls_around_robot = directions_with_scaffold(image.robot.0, &image);
+    let mut walls_around_robot = directions_with_scaffold(image.robot.0, image);
     let (start_dir, start_pos) = walls_around_robot
         .next()
         .ok_or(AoCError::IncorrectInput("robot cannot move anywhere"))?;
@@ -200,7 +200,7 @@ fn create_path(image: &Image) -> Result<Vec<Command>> {
     loop {
         match pos
             .step_in_direction_checked(dir)
-            .filter(|next_pos| image.walls.contains(&next_pos))
+            .filter(|&next_pos| image.walls.contains(next_pos))
         {
             Some(next_pos) => {
                 pos = next_pos;
@@ -210,10 +210,10 @@ fn create_path(image: &Image) -> Result<Vec<Command>> {
                 path.resize(path.len() + current_forward_dist, Command::Move);
                 let right_pos = pos
                     .step_in_direction_checked(dir.clockwise())
-                    .filter(|next_pos| image.walls.contains(&next_pos));
+                    .filter(|&next_pos| image.walls.contains(next_pos));
                 let left_pos = pos
                     .step_in_direction_checked(dir.counterclockwise())
-                    .filter(|next_pos| image.walls.contains(&next_pos));
+                    .filter(|&next_pos| image.walls.contains(next_pos));
                 match (right_pos, left_pos) {
                     (Some(next_pos), None) => {
                         path.push(Command::Right);
@@ -382,9 +382,9 @@ fn create_movement_program(path: &[Command]) -> Option<Program> {
             }
             // Prune matches to accomodate for the next command
             let next_cmd = path[first_range.start + offset];
-            matches.drain_filter(|(range, idx)| {
+            matches.retain(|(range, idx)| {
                 let i = *idx + offset;
-                !range.contains(&i) || path[i] != next_cmd
+                range.contains(&i) && path[i] == next_cmd
             });
             // Don't bother checking substitutions that would consume
             // more than 4 invocations, since there's 3 functions, and
@@ -413,7 +413,7 @@ fn create_movement_program(path: &[Command]) -> Option<Program> {
                         Slice(mut range) => {
                             loop {
                                 // Next item doesn't exist or isn't in range
-                                let idx = match next.clone().filter(|idx| *idx < range.end) {
+                                let idx = match next.filter(|idx| *idx < range.end) {
                                     Some(x) => x,
                                     None => {
                                         new_pts.push(Slice(range));
@@ -453,7 +453,7 @@ fn create_movement_program(path: &[Command]) -> Option<Program> {
         false
     }
 
-    if search_path(&path, &mut progs, &mut pts, 0) {
+    if search_path(path, &mut progs, &mut pts, 0) {
         let main = pts
             .into_iter()
             .map(|pt| match pt {
@@ -474,7 +474,7 @@ fn create_movement_program(path: &[Command]) -> Option<Program> {
 
 #[derive(Debug, Clone)]
 struct Image {
-    walls: HashSet<Vec2>,
+    walls: SparseGrid<()>,
     robot: (Vec2, Direction),
 }
 
@@ -487,7 +487,7 @@ enum Command {
 
 #[test]
 fn day17() -> Result<()> {
-    let mut walls = HashSet::new();
+    let mut walls = SparseGrid::new();
     let robot = (Vec2::new(0, 6), Direction::North);
     for (y, line) in "\
 #######...#####
@@ -511,7 +511,7 @@ fn day17() -> Result<()> {
         for (x, c) in line.chars().enumerate() {
             match c {
                 '#' | '^' | 'v' | '<' | '>' => {
-                    walls.insert(Vec2::new(x, y));
+                    walls.insert(Vec2::new(x as i64, y as i64), ());
                 }
                 '.' => {}
                 _ => unreachable!(),
@@ -1,10 +1,6 @@
-use crate::direction::{Direction, MoveInDirection};
-use crate::graph::{astar_once, AStar};
+use crate::graph::{astar_once, contract_grid, Graph};
 use crate::mat2::Mat2;
 use crate::vec2::Vec2us;
-use crate::HashMap;
-use arrayvec::ArrayVec;
-use num::{One, Zero};
 
 module!(pt1: parse, pt2: parse);
 
@@ -54,115 +50,25 @@ fn find_entrance(layout: &Mat2<Cell>) -> Vec2us {
         .0
 }
 
-#[derive(Debug)]
-struct Node {
-    neighbors: ArrayVec<(Vec2us, usize), 4>,
-    cell: Cell,
-}
-
-/// Turns the grid-based map layout into a graph, preserving only
-/// interesting cells (endpoints, intersection, keys, doors).
-/// It runs two passes. The first pass converts the grid into
-/// nodes, but also creates nodes for corner pieces (nodes with
-/// two neighbors in different directions).
-/// The second pass removes these corners.
-///
-/// On my specific input, this reduces the walkable parts of the
-/// maze from 6561 to 404 on pt1, and from 6561 to 399 for pt2.
-fn grid_to_graph(layout: &Mat2<Cell>) -> HashMap<Vec2us, Node> {
-    let mut nodes: HashMap<Vec2us, Node> = HashMap::new();
-    let mut node_north: Option<Vec2us> = None;
-    let mut nodes_west: Vec<Option<Vec2us>> = Vec::with_capacity(layout.height());
-    nodes_west.resize(layout.height(), None);
-    for x in 0..layout.width() {
-        let column = &layout[x];
-        for y in 0..layout.height() {
-            let cell = column[y];
-            if cell == Cell::Wall {
-                debug_assert!(node_north.is_none());
-                debug_assert!(nodes_west[y].is_none());
-                continue;
-            }
-            let pos = Vec2us::new(x, y);
-            // NSEW
-            let neighbors = Direction::each_arr(|dir| {
-                pos.move_in_bounds(dir, 1, Vec2us::zero(), layout.size() - Vec2us::one())
-                    .map(|pos| match layout[pos] {
-                        Cell::Wall => false,
-                        _ => true,
-                    })
-                    .unwrap_or(false)
-            });
-            // A straight section with no branches doesn't need a node
-            // except when it's a door, key, or the entrance.
-            if match cell {
-                Cell::Door(_) | Cell::Key(_) | Cell::Entrance => false,
-                _ => true,
-            } && neighbors[0] == neighbors[1]
-                && neighbors[2] == neighbors[3]
-                && neighbors[0] != neighbors[2]
-            {
-                continue;
-            }
-            let mut neighbor_positions = ArrayVec::new();
-            if neighbors[0] {
-                let other = node_north.unwrap();
-                let dist = y - other.y;
-                neighbor_positions.push((other, dist));
-                nodes.get_mut(&other).unwrap().neighbors.push((pos, dist));
-            }
-            node_north = if neighbors[1] { Some(pos) } else { None };
-            if neighbors[2] {
-                let other = nodes_west[y].unwrap();
-                let dist = x - other.x;
-                neighbor_positions.push((other, dist));
-                nodes.get_mut(&other).unwrap().neighbors.push((pos, dist));
-            }
-            nodes_west[y] = if neighbors[3] { Some(pos) } else { None };
-            nodes.insert(
-                pos,
-                Node {
-                    neighbors: neighbor_positions,
-                    cell,
-                },
-            );
-        }
-    }
-
-    // Simplify further by removing nodes with exactly 2 neighbors,
-    // unless they are a special node.
-    for point in nodes.keys().cloned().collect::<Vec<_>>() {
-        let node = nodes.get_mut(&point).unwrap();
-        if node.cell != Cell::Open || node.neighbors.len() != 2 {
-            continue;
-        }
-        let dist = node.neighbors[0].1 + node.neighbors[1].1;
-        let a = node.neighbors[0].0;
-        let b = node.neighbors[1].0;
-        nodes.remove(&point);
-
-        for (a, b) in [(a, b), (b, a)].iter().cloned() {
-            let n = nodes.get_mut(&a).unwrap();
-            for neighbor in &mut n.neighbors {
-                if neighbor.0 == point {
-                    neighbor.0 = b;
-                    neighbor.1 = dist;
-                }
-            }
-        }
-    }
-
-    nodes
+/// Turns the maze into a graph of only the interesting cells (keys, doors
+/// and entrances), since every other cell is just a step along the way.
+fn maze_graph(layout: &Mat2<Cell>) -> Graph<Vec2us, usize> {
+    contract_grid(
+        layout,
+        |&cell| cell != Cell::Wall,
+        |&cell| matches!(cell, Cell::Door(_) | Cell::Key(_) | Cell::Entrance),
+    )
 }
 
 fn next<'l>(
-    layout: &'l HashMap<Vec2us, Node>,
+    graph: &'l Graph<Vec2us, usize>,
+    layout: &'l Mat2<Cell>,
     &(pos, key_state): &(Vec2us, KeyState),
 ) -> impl Iterator<Item = ((Vec2us, KeyState), usize)> + 'l {
-    layout[&pos]
-        .neighbors
+    graph
+        .edges(&pos)
         .iter()
-        .filter_map(move |&(next_pos, dist)| match layout[&next_pos].cell {
+        .filter_map(move |&(next_pos, dist)| match layout[next_pos] {
             Cell::Wall => None,
             Cell::Entrance | Cell::Open => Some(((next_pos, key_state), dist)),
             Cell::Door(idx) => {
@@ -188,10 +94,10 @@ fn pt1(map: Map) -> Result<usize> {
     if key_count > 32 {
         return Err(AoCError::Logic("cannot handle more than 32 keys"));
     }
-    let layout = grid_to_graph(&layout);
+    let graph = maze_graph(&layout);
     let path = astar_once(
         (entrance, KeyState::new(key_count)),
-        |s| next(&layout, s),
+        |s| next(&graph, &layout, s),
         heuristic,
         collected_all,
     )
@@ -229,12 +135,12 @@ fn pt2(map: Map) -> Result<usize> {
         layout[entrance] = Cell::Entrance;
     }
 
-    let layout = grid_to_graph(&layout);
+    let graph = maze_graph(&layout);
     let mut keys_after_quadrant = [KeyState::new(key_count); 4];
     layout
         .iter()
-        .filter_map(|(pos, node)| {
-            if let Cell::Key(idx) = node.cell {
+        .filter_map(|(pos, cell)| {
+            if let Cell::Key(idx) = *cell {
                 Some((pos, idx))
             } else {
                 None
@@ -250,120 +156,113 @@ fn pt2(map: Map) -> Result<usize> {
             *keys = keys.with_key(idx);
         });
 
-    let mut astar = AStar::new();
-    let mut total_cost = 0;
-    for (entrance_idx, entrance) in entrances.iter().cloned().enumerate() {
-        let mut start_state = KeyState::new(key_count);
-        keys_after_quadrant
+    // The quadrants don't depend on each other, so each is searched on its
+    // own thread
+    let (graph, layout) = (&graph, &layout);
+    let costs = std::thread::scope(|scope| {
+        let handles: Vec<_> = entrances
             .iter()
+            .cloned()
             .enumerate()
-            .filter(|(idx, _)| *idx != entrance_idx)
-            .for_each(|(_, keys)| start_state = start_state.combine(*keys));
-        let path = astar
-            .solve(
-                (entrance, start_state),
-                |s| next(&layout, s),
-                heuristic,
-                collected_all,
-            )
-            .ok_or(AoCError::NoSolution)?;
-        total_cost += path.last().unwrap().1;
-    }
+            .map(|(entrance_idx, entrance)| {
+                let mut start_state = KeyState::new(key_count);
+                keys_after_quadrant
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| *idx != entrance_idx)
+                    .for_each(|(_, keys)| start_state = start_state.combine(*keys));
+                scope.spawn(move || {
+                    astar_once(
+                        (entrance, start_state),
+                        |s| next(graph, layout, s),
+                        heuristic,
+                        collected_all,
+                    )
+                    .map(|path| path.last().unwrap().1)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect::<Option<Vec<_>>>()
+    });
 
-    Ok(total_cost)
+    Ok(costs.ok_or(AoCError::NoSolution)?.iter().sum())
 }
 
-fn parse(s: &str) -> IResult<&str, Map> {
-    use parsers::*;
-    fn cell(s: &str) -> IResult<&str, Cell> {
-        let c = s.chars().next().ok_or(Err::Error(nom::error::Error::new(s, ErrorKind::Eof)))?;
-        Ok((
-            &s[1..],
-            match c {
-                'A'..='Z' => Cell::Door(c as u8 - b'A'),
-                'a'..='z' => Cell::Key(c as u8 - b'a'),
-                '.' => Cell::Open,
-                '#' => Cell::Wall,
-                '@' => Cell::Entrance,
-                _ => return Err(Err::Error(nom::error::Error::new(s, ErrorKind::OneOf))),
-            },
-        ))
-    }
-
-    map_res(
-        terminated(many1(terminated(many1(cell), line_ending_or_eof)), eof),
-        |maze| {
-            let size = Vec2us::new(maze[0].len(), maze.len());
-            if maze[1..].iter().any(|row| row.len() != size.x) {
-                return Err(AoCError::IncorrectInput(
-                    "cannot have different line widths",
-                ));
-            }
-            let mut cells = Mat2::new(Cell::Wall, size);
-            for (y, row) in maze.into_iter().enumerate() {
-                for (x, cell) in row.into_iter().enumerate() {
-                    cells[Vec2us::new(x, y)] = cell;
+fn parse(s: &str) -> Result<Map> {
+    let cells = Mat2::from_text(s, |c| {
+        Ok(match c {
+            'A'..='Z' => Cell::Door(c as u8 - b'A'),
+            'a'..='z' => Cell::Key(c as u8 - b'a'),
+            '.' => Cell::Open,
+            '#' => Cell::Wall,
+            '@' => Cell::Entrance,
+            _ => return Err(AoCError::IncorrectInput("unexpected character in maze")),
+        })
+    })?;
+    let mut door_count = 0;
+    let mut doors = Vec::new();
+    let mut has_entrance = false;
+    for cell in &cells.data {
+        match cell {
+            Cell::Door(v) => {
+                let v = *v as usize;
+                if v >= doors.len() {
+                    doors.resize(v + 1, false);
                 }
-            }
-            let mut door_count = 0;
-            let mut doors = Vec::new();
-            let mut has_entrance = false;
-            for cell in &cells.data {
-                match cell {
-                    Cell::Door(v) => {
-                        let v = *v as usize;
-                        if v >= doors.len() {
-                            doors.resize(v + 1, false);
-                        }
-                        if doors[v] {
-                            return Err(AoCError::IncorrectInput(
-                                "multiple doors for the same key",
-                            ));
-                        }
-                        door_count += 1;
-                        doors[v] = true;
-                    }
-                    Cell::Entrance => {
-                        if has_entrance {
-                            return Err(AoCError::IncorrectInput("multiple entrances"));
-                        }
-                        has_entrance = true;
-                    }
-                    _ => {}
+                if doors[v] {
+                    return Err(AoCError::IncorrectInput(
+                        "multiple doors for the same key",
+                    ));
                 }
+                door_count += 1;
+                doors[v] = true;
             }
-            if !has_entrance {
-                return Err(AoCError::IncorrectInput("no entrance in input"));
-            }
-            if door_count == 0 {
-                return Err(AoCError::IncorrectInput("no keys/doors in input"));
-            }
-            if door_count != doors.len() {
-                return Err(AoCError::IncorrectInput("non-consecutive door numbers"));
-            }
-            let mut key_count = 0;
-            for cell in &cells.data {
-                if let Cell::Key(v) = cell {
-                    let v = *v as usize;
-                    if v >= doors.len() || !doors[v] {
-                        return Err(AoCError::IncorrectInput(
-                            "multiple keys for door or key without door",
-                        ));
-                    }
-                    doors[v] = false;
-                    key_count += 1;
+            Cell::Entrance => {
+                if has_entrance {
+                    return Err(AoCError::IncorrectInput("multiple entrances"));
                 }
+                has_entrance = true;
             }
-            if door_count != key_count {
-                return Err(AoCError::IncorrectInput("not every door has a key"));
+            _ => {}
+        }
+    }
+    if !has_entrance {
+        return Err(AoCError::IncorrectInput("no entrance in input"));
+    }
+    if door_count == 0 {
+        return Err(AoCError::IncorrectInput("no keys/doors in input"));
+    }
+    if door_count != doors.len() {
+        return Err(AoCError::IncorrectInput("non-consecutive door numbers"));
+    }
+    let mut key_count = 0;
+    for cell in &cells.data {
+        if let Cell::Key(v) = cell {
+            let v = *v as usize;
+            if v >= doors.len() || !doors[v] {
+                return Err(AoCError::IncorrectInput(
+                    "multiple keys for door or key without door",
+                ));
             }
+            doors[v] = false;
+            key_count += 1;
+        }
+    }
+    if door_count != key_count {
+        return Err(AoCError::IncorrectInput("not every door has a key"));
+    }
 
-            Ok(Map {
-                layout: cells,
-                key_count: key_count as u8,
-            })
-        },
-    )(s)
+    Ok(Map {
+        layout: cells,
+        key_count: key_count as u8,
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -383,16 +282,43 @@ enum Cell {
 
 #[test]
 fn day18() -> Result<()> {
+    use crate::graph::{all_pairs_dijkstra, dijkstra, floyd_warshall};
+    let map = parse(include_str!("../data/examples/day18/corridor.txt"))?;
+    let entrance = find_entrance(&map.layout);
+    let graph = maze_graph(&map.layout);
+    // The `B` door in the wall is a node without any edges
+    assert_eq!(graph.len(), 5);
+    let next = |pos: &Vec2us| graph.neighbors(pos);
+
+    // Ignoring the doors
+    let distances = dijkstra(entrance, next);
     assert_eq!(
-        8,
-        pt1(parse(
-            "\
-#########
-#b.A.@.a#
-B########"
-        )?
-        .1)?
+        (distances[&Vec2us::new(7, 1)], distances[&Vec2us::new(1, 1)]),
+        (2, 4)
     );
 
+    let nodes: Vec<_> = graph.nodes().cloned().collect();
+    let all_pairs = floyd_warshall(&nodes, next);
+    let repeated = all_pairs_dijkstra(nodes, next);
+    assert_eq!(all_pairs, repeated);
+    Ok(())
+}
+
+#[test]
+fn ida_star_matches_astar() -> Result<()> {
+    use crate::graph::ida_star;
+    for input in [
+        include_str!("../data/examples/day18/corridor.txt"),
+        include_str!("../data/examples/day18/keys.txt"),
+    ] {
+        let Map { layout, key_count } = parse(input)?;
+        let graph = maze_graph(&layout);
+        let start = (find_entrance(&layout), KeyState::new(key_count));
+        let next = |s: &(Vec2us, KeyState)| next(&graph, &layout, s);
+
+        let expected = astar_once(start, next, heuristic, collected_all).unwrap();
+        let path = ida_star(start, next, heuristic, collected_all).unwrap();
+        assert_eq!(path.last(), expected.last());
+    }
     Ok(())
 }
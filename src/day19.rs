@@ -42,7 +42,7 @@ fn pt1(memory: Vec<Value>) -> Result<usize> {
     (0..50)
         .flat_map(|x| (0..50).map(move |y| Vec2us::new(x, y)))
         .map(|pos| is_in_tractor_beam(&memory, &mut vm, pos))
-        .fold(Ok(0), |acc, val| Ok(acc? + if val? { 1 } else { 0 }))
+        .try_fold(0, |acc, val| Ok(acc + if val? { 1 } else { 0 }))
 }
 
 fn pt2(memory: Vec<Value>) -> Result<usize> {
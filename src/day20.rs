@@ -1,5 +1,6 @@
 use crate::direction::{Direction, MoveInDirection};
-use crate::graph::bfs_meta;
+use crate::graph::{astar_once, bidirectional_bfs, contract_grid, Graph};
+use crate::mat2::Mat2;
 use crate::vec2::{AabbIteratorEx, Vec2us};
 use crate::HashMap;
 use std::collections::hash_map::Entry;
@@ -8,31 +9,17 @@ use std::hash::Hash;
 
 module!(pt1: parse, pt2: parse);
 
+/// The open cells next to `pos`, and the teleport destination if `pos` is a
+/// teleport point.
+fn walk(map: &Map, pos: Vec2us) -> impl Iterator<Item = Vec2us> + '_ {
+    Direction::each()
+        .filter_map(move |dir| pos.step_in_direction_checked(dir))
+        .filter(move |pos| matches!(map.layout.get(pos), Some(cell) if *cell != Cell::Wall))
+        .chain(map.tps.get(&pos).cloned())
+}
+
 fn pt1(map: Map) -> Result<usize> {
-    let mut res = None;
-    bfs_meta(
-        (map.entrance, 0),
-        |&pos, &dist| {
-            Direction::each()
-                .filter_map(move |dir| pos.step_in_direction_checked(dir))
-                .filter(|pos| match map.layout.get(&pos) {
-                    Some(Cell::Wall) => false,
-                    None => false,
-                    Some(_) => true,
-                })
-                .chain(std::iter::once(map.tps.get(&pos).cloned()).filter_map(|x| x))
-                .map(move |pos| (pos, dist + 1))
-        },
-        |&pos, &dist| {
-            if pos == map.exit {
-                res = Some(dist);
-                true
-            } else {
-                false
-            }
-        },
-    );
-    res.ok_or(AoCError::NoSolution)
+    bidirectional_bfs(map.entrance, map.exit, |&pos| walk(&map, pos)).ok_or(AoCError::NoSolution)
 }
 
 fn pt2(map: Map) -> Result<usize> {
@@ -46,11 +33,10 @@ fn pt2(map: Map) -> Result<usize> {
             return Err(AoCError::IncorrectInput("teleport points do not lie on border and inner side"));
         }
     }
-    
-    let mut res = None;
-    bfs_meta(
-        ((map.entrance, 0), 0),
-        |&(pos, layer), &dist| {
+
+    let path = astar_once(
+        (map.entrance, 0),
+        |&(pos, layer)| {
             let tp_to_pos = map.tps.get(&pos).cloned().and_then(|p| {
                 if is_outer_teleport(pos) {
                     if layer == 0 {
@@ -63,64 +49,63 @@ fn pt2(map: Map) -> Result<usize> {
                 }
             });
 
-            Direction::each()
-                .filter_map(move |dir| pos.step_in_direction_checked(dir))
-                .filter(|pos| match map.layout.get(&pos) {
-                    Some(Cell::Wall) => false,
-                    None => false,
-                    Some(_) => true,
-                })
-                .map(move |pos| (pos, layer))
-                .chain(std::iter::once(tp_to_pos).filter_map(|x| x))
-                .map(move |n| (n, dist + 1))
-        },
-        |&(pos, layer), &dist| {
-            if layer == 0 && pos == map.exit {
-                res = Some(dist);
-                true
-            } else {
-                false
-            }
+            map.graph
+                .neighbors(&pos)
+                .map(move |(pos, dist)| ((pos, layer), dist))
+                .chain(tp_to_pos.map(|n| (n, 1)))
         },
-    );
-    res.ok_or(AoCError::NoSolution)
+        |_| 0,
+        |&(pos, layer)| layer == 0 && pos == map.exit,
+    )
+    .ok_or(AoCError::NoSolution)?;
+    Ok(path.last().map_or(0, |(_, dist)| *dist))
 }
 
-fn parse(s: &str) -> Result<Map> {
-    let mut tp_chars = Vec::new();
-    let mut layout = HashMap::new();
-    for (y, line) in s.as_bytes().split(|&c| c == b'\n').enumerate() {
-        for (x, c) in line.iter().cloned().enumerate() {
-            let pos = Vec2us::new(x, y);
-            match c {
-                b' ' => {}
-                b'#' => assert!(layout.insert(pos, Cell::Wall).is_none()),
-                b'.' => assert!(layout.insert(pos, Cell::Open).is_none()),
-                b'A'..=b'Z' => tp_chars.push((pos, c)),
-                _ => return Err(AoCError::IncorrectInput("unexpected char in input")),
-            }
-        }
+/// Contracts the maze to the entrance, the exit and the teleport points.
+fn portal_graph(layout: &HashMap<Vec2us, Cell>) -> Graph<Vec2us, usize> {
+    let (_, max) = layout.keys().cloned().aabb().unwrap();
+    let mut grid = Mat2::new(Cell::Wall, max + Vec2us::new(1, 1));
+    for (&pos, &cell) in layout {
+        grid[pos] = cell;
     }
+    contract_grid(
+        &grid,
+        |&cell| cell != Cell::Wall,
+        |&cell| matches!(cell, Cell::TeleportPoint(_) | Cell::Entrance | Cell::Exit),
+    )
+}
 
-    tp_chars.sort_unstable_by_key(|(pos, _)| *pos);
+fn parse(s: &str) -> Result<Map> {
+    // The labels stick out of the maze and the lines aren't padded, so the
+    // grid is filled up with blanks
+    let chars = Mat2::from_text_padded(s, b' ', |c| match c {
+        ' ' | '#' | '.' | 'A'..='Z' => Ok(c as u8),
+        _ => Err(AoCError::IncorrectInput("unexpected char in input")),
+    })?;
+    let mut layout: HashMap<Vec2us, Cell> = chars
+        .iter()
+        .filter_map(|(pos, &c)| match c {
+            b'#' => Some((pos, Cell::Wall)),
+            b'.' => Some((pos, Cell::Open)),
+            _ => None,
+        })
+        .collect();
+    let is_letter = |pos: Vec2us| {
+        pos.x < chars.width() && pos.y < chars.height() && chars[pos].is_ascii_uppercase()
+    };
 
+    // The cells are visited in order, so the first letter of a name is always
+    // seen before the second one
     let mut tp_points = HashMap::new();
-    for (pos, c) in tp_chars.iter().cloned() {
+    for (pos, &c) in chars.iter().filter(|(_, c)| c.is_ascii_uppercase()) {
         let mut valid_neighbors = Direction::each()
             .filter_map(|dir| pos.step_in_direction_checked(dir))
-            .filter_map(|pos| {
-                tp_chars
-                    .binary_search_by_key(&pos, |(pos, _)| *pos)
-                    .ok()
-                    .map(|idx| tp_chars[idx])
-            });
+            .filter(|&pos| is_letter(pos));
 
-        if valid_neighbors.clone().count() == 0 {
-            println!("{}", pos);
-        }
-        let (neighbor_pos, neighbor_c) = valid_neighbors
+        let neighbor_pos = valid_neighbors
             .next()
             .ok_or(AoCError::IncorrectInput("teleport point with no neighbors"))?;
+        let neighbor_c = chars[neighbor_pos];
         if valid_neighbors.next().is_some() {
             return Err(AoCError::IncorrectInput(
                 "teleport point with multiple neighbors",
@@ -153,8 +138,8 @@ fn parse(s: &str) -> Result<Map> {
             (Some(_), Some(_)) => {
                 return Err(AoCError::IncorrectInput("teleport has two connected cells"))
             }
-            (Some(p), None) if p == Cell::Open => pos.step_in_direction(before),
-            (None, Some(p)) if p == Cell::Open => pos.move_in_direction(after, 2),
+            (Some(Cell::Open), None) => pos.step_in_direction(before),
+            (None, Some(Cell::Open)) => pos.move_in_direction(after, 2),
             _ => return Err(AoCError::IncorrectInput("teleport cell isn't open")),
         };
 
@@ -201,6 +186,7 @@ fn parse(s: &str) -> Result<Map> {
     let exit = exit.ok_or(AoCError::IncorrectInput("no exit"))?;
 
     Ok(Map {
+        graph: portal_graph(&layout),
         layout,
         tps,
         entrance,
@@ -245,6 +231,7 @@ enum Cell {
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Map {
     layout: HashMap<Vec2us, Cell>,
+    graph: Graph<Vec2us, usize>,
     tps: HashMap<Vec2us, Vec2us>,
     entrance: Vec2us,
     exit: Vec2us,
@@ -252,47 +239,29 @@ struct Map {
 
 #[test]
 fn day20() -> Result<()> {
-    const EXAMPLE: &'static str = "
-             Z L X W       C                 
-             Z P Q B       K                 
-  ###########.#.#.#.#######.###############  
-  #...#.......#.#.......#.#.......#.#.#...#  
-  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###  
-  #.#...#.#.#...#.#.#...#...#...#.#.......#  
-  #.###.#######.###.###.#.###.###.#.#######  
-  #...#.......#.#...#...#.............#...#  
-  #.#########.#######.#.#######.#######.###  
-  #...#.#    F       R I       Z    #.#.#.#  
-  #.###.#    D       E C       H    #.#.#.#  
-  #.#...#                           #...#.#  
-  #.###.#                           #.###.#  
-  #.#....OA                       WB..#.#..ZH
-  #.###.#                           #.#.#.#  
-CJ......#                           #.....#  
-  #######                           #######  
-  #.#....CK                         #......IC
-  #.###.#                           #.###.#  
-  #.....#                           #...#.#  
-  ###.###                           #.#.#.#  
-XF....#.#                         RF..#.#.#  
-  #####.#                           #######  
-  #......CJ                       NM..#...#  
-  ###.#.#                           #.###.#  
-RE....#.#                           #......RF
-  ###.###        X   X       L      #.#.#.#  
-  #.....#        F   Q       P      #.#.#.#  
-  ###.###########.###.#######.#########.###  
-  #.....#...#.....#.......#...#.....#.#...#  
-  #####.#.###.#######.#######.###.###.#.#.#  
-  #.......#.......#.#.#.#.#...#...#...#.#.#  
-  #####.###.#####.#.#.#.#.###.###.#.###.###  
-  #.......#.....#.#...#...............#...#  
-  #############.#.#.###.###################  
-               A O F   N                     
-               A A D   M                     ";
+    use crate::graph::{all_pairs_dijkstra, dijkstra, floyd_warshall};
+    let map = parse(include_str!("../data/examples/day20/small.txt"))?;
+    // The entrance, the exit and the 3 pairs of teleport points
+    assert_eq!(map.graph.len(), 8);
 
-    let map = parse(EXAMPLE)?;
-    assert_eq!(pt2(map)?, 396);
+    // The portal graph gives the same distances as walking cell by cell
+    assert_eq!(
+        bidirectional_bfs(map.entrance, map.exit, |&pos| walk(&map, pos)),
+        Some(23)
+    );
+    let next = |pos: &Vec2us| {
+        map.graph
+            .neighbors(pos)
+            .chain(map.tps.get(pos).map(|&to| (to, 1)))
+    };
+    let distances = dijkstra(map.entrance, next);
+    assert_eq!(distances[&map.exit], 23);
 
+    let cells: Vec<_> = map.graph.nodes().cloned().collect();
+    let all_pairs = floyd_warshall(&cells, next);
+    assert_eq!(all_pairs[&map.entrance], distances);
+    assert_eq!(all_pairs[&map.exit][&map.entrance], 23);
+    let repeated = all_pairs_dijkstra(vec![map.entrance, map.exit], next);
+    assert_eq!(repeated[&map.exit], all_pairs[&map.exit]);
     Ok(())
 }
@@ -97,7 +97,7 @@ fn get_mul_add_to_reverse_shuffle(steps: &[Technique], deck_size: i128) -> (i128
                     }) % deck_size;
             }
             Technique::DealWithIncrement(increment) => {
-                let x = multiplicative_inverse(increment as i128, deck_size as i128);
+                let x = multiplicative_inverse(increment as i128, deck_size);
                 mul = (mul * x) % deck_size;
                 add = (add * x) % deck_size;
             }
@@ -108,7 +108,7 @@ fn get_mul_add_to_reverse_shuffle(steps: &[Technique], deck_size: i128) -> (i128
 }
 
 fn modular_pow(mut base: u128, mut exp: u128, modulus: u128) -> u128 {
-    assert!(modulus > 0 && (modulus - 1) < std::u64::MAX as u128);
+    assert!(modulus > 0 && (modulus - 1) < u64::MAX as u128);
     if modulus == 1 {
         return 0;
     }
@@ -218,9 +218,9 @@ fn day22() {
 
     for i in 1..=10 {
         iter(deck);
-        for j in 0..deck.len() {
+        for (j, &card) in deck.iter().enumerate() {
             assert_eq!(
-                deck[j] as i128,
+                card as i128,
                 nr_in_position_after(steps, j as i128, 101, i)
             );
         }
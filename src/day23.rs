@@ -5,19 +5,19 @@ use std::collections::VecDeque;
 module!(pt1: parse_intcode, pt2: parse_intcode);
 
 #[derive(Debug, Clone)]
-struct NIC {
+struct Nic {
     vm: VM<GrowingMemory>,
     output_batch: ArrayVec<Value, 3>,
     did_yield: bool,
 }
 
-fn init_network(input: &[Value]) -> (Vec<VecDeque<Value>>, Vec<NIC>) {
+fn init_network(input: &[Value]) -> (Vec<VecDeque<Value>>, Vec<Nic>) {
     let mut input_queues = vec![VecDeque::new(); 50];
     for i in 0..50 {
         input_queues[i as usize].push_back(i as Value);
     }
     let nics = vec![
-        NIC {
+        Nic {
             vm: VM::new(growing_memory(input.to_owned())),
             output_batch: ArrayVec::new(),
             did_yield: false,
@@ -60,7 +60,7 @@ fn pt1(input: Vec<Value>) -> Result<Value> {
                         if addr == 255 && result.is_none() {
                             result = Some(y);
                         }
-                        if addr >= 0 && addr < 50 {
+                        if (0..50).contains(&addr) {
                             input_queues[addr as usize].push_back(x);
                             input_queues[addr as usize].push_back(y);
                         }
@@ -112,7 +112,7 @@ fn pt2(input: Vec<Value>) -> Result<Value> {
                         if addr == 255 {
                             nat = (x, y);
                         }
-                        if addr >= 0 && addr < 50 {
+                        if (0..50).contains(&addr) {
                             input_queues[addr as usize].push_back(x);
                             input_queues[addr as usize].push_back(y);
                         }
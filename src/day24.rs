@@ -1,4 +1,6 @@
-use crate::HashSet;
+use crate::cycle;
+use crate::mat2::Mat2;
+use crate::vec2::Vec2us;
 use std::fmt;
 
 module!(pt1: parse, pt2: parse);
@@ -88,26 +90,16 @@ impl ErisBugs {
 
 impl fmt::Display for ErisBugs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use fmt::Write;
-        for y in 0..5 {
-            for x in 0..5 {
-                let i = x + y * 5;
-                f.write_char(if (self.0 >> i) & 1 == 1 { '#' } else { '.' })?;
-            }
-            f.write_char('\n')?;
-        }
-        Ok(())
+        let grid = Mat2::from_fn(Vec2us::new(5, 5), |pos| {
+            (self.0 >> (pos.x + pos.y * 5)) & 1 == 1
+        });
+        f.write_str(&grid.render(|&bug| if bug { '#' } else { '.' }))
     }
 }
 
-fn pt1(mut bugs: ErisBugs) -> u32 {
-    let mut seen = HashSet::new();
-    loop {
-        if !seen.insert(bugs) {
-            break bugs.0;
-        }
-        bugs = bugs.grow();
-    }
+fn pt1(bugs: ErisBugs) -> u32 {
+    let (_, repeated) = cycle::hashed(bugs, |bugs| bugs.grow());
+    repeated.0
 }
 
 fn trim_left_matches<T, F>(mut slice: &[T], mut predicate: F) -> &[T]
@@ -152,7 +144,7 @@ fn grow_recursive_levels(levels: &mut Vec<ErisBugs>, buffer: &mut Vec<ErisBugs>)
     // at the start and end.
     buffer.clear();
     buffer.push(ErisBugs(0));
-    let slice = trim_matches(&levels, |bugs| bugs.0 == 0);
+    let slice = trim_matches(levels, |bugs| bugs.0 == 0);
     buffer.extend_from_slice(slice);
     buffer.push(ErisBugs(0));
 
@@ -177,26 +169,23 @@ fn pt2(init: ErisBugs) -> u32 {
 }
 
 fn parse(s: &str) -> Result<ErisBugs> {
-    let mut chars = s.chars();
+    // The examples of part 2 mark the center of a level with `?`
+    let grid = Mat2::from_text(s, |c| match c {
+        '.' => Ok(Some(false)),
+        '#' => Ok(Some(true)),
+        '?' => Ok(None),
+        _ => Err(AoCError::IncorrectInput("invalid char, expected . or #")),
+    })?;
+    if grid.size() != Vec2us::new(5, 5) {
+        return Err(AoCError::IncorrectInput("expected 5 lines of 5 chars"));
+    }
     let mut state = 0;
-    for y in 0..5 {
-        for x in 0..5 {
-            match chars.next() {
-                Some('.') => {}
-                Some('#') => state |= 1 << (x + y * 5),
-                Some('?') if x == 2 && y == 2 => {}
-                _ => return Err(AoCError::IncorrectInput("invalid char, expected . or #")),
-            }
-        }
-        if y == 4 {
-            if chars.next().is_some() {
-                return Err(AoCError::IncorrectInput("expected end of input"));
-            }
-        } else {
-            match chars.next() {
-                Some('\n') => {}
-                _ => return Err(AoCError::IncorrectInput("expected end of line")),
-            }
+    for (pos, &cell) in grid.iter() {
+        match cell {
+            Some(true) => state |= 1 << (pos.x + pos.y * 5),
+            Some(false) => {}
+            None if pos == Vec2us::new(2, 2) => {}
+            None => return Err(AoCError::IncorrectInput("? is only allowed in the center")),
         }
     }
     Ok(ErisBugs(state))
@@ -213,6 +202,12 @@ fn day24() -> Result<()> {
 .#...")?,
         ErisBugs(2129920)
     );
+    assert_eq!(
+        ErisBugs(2129920).to_string(),
+        ".....\n.....\n.....\n#....\n.#...\n"
+    );
+    assert!(parse(".....\n..?..\n.....\n.....\n.....").is_err());
+    assert!(parse(".....\n.....\n.....\n.....").is_err());
 
     #[rustfmt::skip] assert_eq!(
         parse("\
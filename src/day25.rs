@@ -1,5 +1,5 @@
 use crate::direction::Direction;
-use crate::graph::AStar;
+use crate::graph::{available_threads, parallel_bfs, AStar};
 use crate::intcode::{growing_memory, util::parse_intcode, GrowingMemory, State, Value, VM};
 use crate::{Atom, HashMap, HashSet};
 use arrayvec::ArrayVec;
@@ -58,7 +58,7 @@ impl AdventureVM {
                 }
                 State::Writing => {
                     let value = self.vm.registers.pending_out.unwrap();
-                    if value < 0 || value >= 128 {
+                    if !(0..128).contains(&value) {
                         return Err(AoCError::IncorrectInput(
                             "program outputted non-ascii character",
                         ));
@@ -162,13 +162,15 @@ impl Adventure {
             if let Some(idx) =
                 room.items
                     .iter()
-                    .position(|item| match <Atom as std::ops::Deref>::deref(item) {
-                        "escape pod" => false,
-                        "giant electromagnet" => false,
-                        "infinite loop" => false,
-                        "molten lava" => false,
-                        "photons" => false,
-                        _ => true,
+                    .position(|item| {
+                        !matches!(
+                            <Atom as std::ops::Deref>::deref(item),
+                            "escape pod"
+                                | "giant electromagnet"
+                                | "infinite loop"
+                                | "molten lava"
+                                | "photons"
+                        )
                     })
             {
                 let item = room.items.remove(idx);
@@ -184,7 +186,7 @@ impl Adventure {
                 .iter()
                 .filter_map(
                     |(dir, next_name)| {
-                        if next_name == "" {
+                        if next_name.is_empty() {
                             Some(*dir)
                         } else {
                             None
@@ -221,76 +223,61 @@ impl Adventure {
             self.rooms[&self.current_room]
                 .connections
                 .iter()
-                .find(|(_, room)| room == "")
+                .find(|(_, room)| room.is_empty())
                 .unwrap()
                 .0,
         );
 
         assert!(self.inventory.len() < 32);
-        let mut carrying = (1u32 << self.inventory.len()) - 1;
         let inventory = self.inventory.iter().cloned().collect::<Vec<_>>();
-        let mut is_moving = false;
-        
-        let solved = self.vm.resume(|_input, output| {
-            is_moving = !is_moving;
-            if is_moving {
-                output.push_str(move_dir);
-                Ok(true)
-            } else if carrying == 0 {
-                Ok(false)
-            } else {
-                let diffs = carrying ^ (carrying - 1);
-                assert_ne!(diffs, 0);
-                carrying -= 1;
-                for (idx, item) in inventory.iter().enumerate() {
-                    if (diffs >> idx) & 1 == 1 {
-                        output.push_str(if (carrying >> idx) & 1 == 1 {
-                            "take "
-                        } else {
-                            "drop "
-                        });
-                        output.push_str(item);
-                        output.push('\n');
-                    }
-                }
-                output.pop();
-                Ok(true)
-            }
-        })?;
-        if solved {
-            let beg = self
-                .vm
-                .out_buff
-                .find("Oh, hello! You should be able to get in by typing ")
-                .ok_or(AoCError::IncorrectInput("unexpected outcome"))?;
-            let end = self
-                .vm
-                .out_buff
-                .find(" on the keypad at the main airlock.")
-                .ok_or(AoCError::IncorrectInput("unexpected outcome"))?;
-            let code = &self.vm.out_buff[beg + 50..end];
-
-            let mut carried = inventory
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, item)| {
-                    if (carrying >> idx) & 1 == 1 {
-                        Some(<Atom as std::ops::Deref>::deref(item))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            carried.sort_unstable();
-            return Ok(format!(
-                "Code: {}, with items: {}",
-                code,
-                carried.join(", ")
-            ));
-        }
+        let all_items = (1u32 << inventory.len()) - 1;
 
-        Err(AoCError::Logic(
+        // Each combination is tried on its own copy of the VM, starting from
+        // carrying everything and dropping one more item in every layer
+        let vm = &self.vm;
+        let carrying = parallel_bfs(
+            all_items,
+            |&carrying| {
+                (0..inventory.len())
+                    .filter(move |idx| (carrying >> idx) & 1 == 1)
+                    .map(move |idx| carrying & !(1 << idx))
+            },
+            |&carrying| matches!(try_items(vm, &inventory, carrying, move_dir), Ok(Some(_))),
+            available_threads(),
+        )
+        .and_then(|path| path.last().cloned())
+        .ok_or(AoCError::Logic(
             "couldn't get past pressure sensitive floor",
+        ))?;
+        let vm = try_items(vm, &inventory, carrying, move_dir)?
+            .ok_or(AoCError::Logic("combination of items stopped working"))?;
+
+        let beg = vm
+            .out_buff
+            .find("Oh, hello! You should be able to get in by typing ")
+            .ok_or(AoCError::IncorrectInput("unexpected outcome"))?;
+        let end = vm
+            .out_buff
+            .find(" on the keypad at the main airlock.")
+            .ok_or(AoCError::IncorrectInput("unexpected outcome"))?;
+        let code = &vm.out_buff[beg + 50..end];
+
+        let mut carried = inventory
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| {
+                if (carrying >> idx) & 1 == 1 {
+                    Some(<Atom as std::ops::Deref>::deref(item))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        carried.sort_unstable();
+        Ok(format!(
+            "Code: {}, with items: {}",
+            code,
+            carried.join(", ")
         ))
     }
 
@@ -314,7 +301,7 @@ impl Adventure {
             .iter()
             .zip(path.iter().skip(1))
             .map(|((prev, _), (next, _))| {
-                rooms[&prev]
+                rooms[prev]
                     .connections
                     .iter()
                     .find(|(_, name)| name == next)
@@ -335,6 +322,37 @@ impl Adventure {
     }
 }
 
+/// Drops the items that aren't in `carrying` on a copy of the VM, and then
+/// tries to move past the pressure sensitive floor. Returns the VM if that
+/// succeeded.
+fn try_items(
+    vm: &AdventureVM,
+    inventory: &[Atom],
+    carrying: u32,
+    move_dir: &str,
+) -> Result<Option<AdventureVM>> {
+    let mut vm = vm.clone();
+    let mut drops = inventory
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| (carrying >> idx) & 1 == 0)
+        .map(|(_, item)| item);
+    let mut moved = false;
+    let solved = vm.resume(|_input, output| {
+        if let Some(item) = drops.next() {
+            output.push_str("drop ");
+            output.push_str(item);
+        } else if !moved {
+            output.push_str(move_dir);
+            moved = true;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    Ok(if solved { Some(vm) } else { None })
+}
+
 fn dir_to_str(dir: Direction) -> &'static str {
     match dir {
         Direction::North => "north",
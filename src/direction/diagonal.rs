@@ -0,0 +1,131 @@
+use super::{Direction, GridDirection, StepInDirection};
+use crate::vec2::Vec2;
+use num::{CheckedAdd, CheckedSub, One};
+
+/// The four directions of `Direction` along with the diagonals between them,
+/// where north is towards negative `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// The offset of a step in this direction, as `(x, y)`.
+    #[inline]
+    pub fn offset(self) -> (i8, i8) {
+        use Direction8::*;
+        match self {
+            North => (0, -1),
+            NorthEast => (1, -1),
+            East => (1, 0),
+            SouthEast => (1, 1),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            NorthWest => (-1, -1),
+        }
+    }
+
+    #[inline]
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Turns an eighth of a turn clockwise.
+    pub fn clockwise(self) -> Direction8 {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+    /// Turns an eighth of a turn counterclockwise.
+    pub fn counterclockwise(self) -> Direction8 {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+}
+
+impl GridDirection for Direction8 {
+    /// Clockwise, starting at `North`.
+    const ALL: &'static [Direction8] = &[
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    #[inline]
+    fn reverse(self) -> Direction8 {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        match direction {
+            Direction::North => Direction8::North,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+            Direction::East => Direction8::East,
+        }
+    }
+}
+
+/// Adds an offset of -1, 0 or 1.
+#[inline]
+pub(super) fn offset_checked<I>(value: &I, offset: i8) -> Option<I>
+where
+    I: Clone + One + CheckedAdd + CheckedSub,
+{
+    match offset {
+        -1 => value.checked_sub(&I::one()),
+        0 => Some(value.clone()),
+        1 => value.checked_add(&I::one()),
+        _ => unreachable!(),
+    }
+}
+
+impl<I> StepInDirection<Direction8> for Vec2<I>
+where
+    I: Clone + One + CheckedAdd + CheckedSub,
+{
+    #[inline]
+    fn step_checked(&self, direction: Direction8) -> Option<Self> {
+        let (dx, dy) = direction.offset();
+        Some(Vec2::new(
+            offset_checked(&self.x, dx)?,
+            offset_checked(&self.y, dy)?,
+        ))
+    }
+}
+
+#[test]
+fn direction8() {
+    use super::neighbors;
+    use crate::vec2::Vec2us;
+    assert_eq!(Direction8::NorthWest.clockwise(), Direction8::North);
+    assert_eq!(Direction8::North.counterclockwise(), Direction8::NorthWest);
+    assert_eq!(Direction8::SouthWest.reverse(), Direction8::NorthEast);
+    assert_eq!(Direction8::from(Direction::West), Direction8::West);
+    assert!(Direction8::ALL
+        .iter()
+        .all(|dir| dir.reverse().reverse() == *dir));
+
+    let corner: Vec<_> = neighbors::<Direction8, _>(Vec2us::new(0, 0)).collect();
+    assert_eq!(
+        corner,
+        [
+            (Direction8::East, Vec2us::new(1, 0)),
+            (Direction8::SouthEast, Vec2us::new(1, 1)),
+            (Direction8::South, Vec2us::new(0, 1)),
+        ]
+    );
+    assert_eq!(neighbors::<Direction8, _>(Vec2us::new(1, 1)).count(), 8);
+}
@@ -0,0 +1,138 @@
+use super::{GridDirection, StepInDirection};
+use crate::vec3::Vec3;
+
+/// The six directions on a hexagonal grid of which the hexagons have flat
+/// tops, so every hexagon has a neighbor straight above and below it. A grid
+/// of hexagons with pointy tops is the same grid turned a twelfth of a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// The offset of a step in this direction, as `(q, r)`.
+    #[inline]
+    pub fn offset(self) -> (i64, i64) {
+        use HexDirection::*;
+        match self {
+            North => (0, -1),
+            NorthEast => (1, -1),
+            SouthEast => (1, 0),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            NorthWest => (-1, 0),
+        }
+    }
+
+    /// Turns a sixth of a turn clockwise.
+    pub fn clockwise(self) -> HexDirection {
+        Self::ALL[(self as usize + 1) % 6]
+    }
+    /// Turns a sixth of a turn counterclockwise.
+    pub fn counterclockwise(self) -> HexDirection {
+        Self::ALL[(self as usize + 5) % 6]
+    }
+}
+
+impl GridDirection for HexDirection {
+    /// Clockwise, starting at `North`.
+    const ALL: &'static [HexDirection] = &[
+        HexDirection::North,
+        HexDirection::NorthEast,
+        HexDirection::SouthEast,
+        HexDirection::South,
+        HexDirection::SouthWest,
+        HexDirection::NorthWest,
+    ];
+
+    #[inline]
+    fn reverse(self) -> HexDirection {
+        Self::ALL[(self as usize + 3) % 6]
+    }
+}
+
+/// A hexagon in axial coordinates, where `q` increases towards the south east
+/// and `r` towards the south. The third cube coordinate is implied, since the
+/// three of them always sum to 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    #[inline(always)]
+    pub const fn new(q: i64, r: i64) -> Self {
+        Hex { q, r }
+    }
+
+    /// The third cube coordinate, which increases towards the north west.
+    #[inline]
+    pub fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// Converts cube coordinates `(q, r, s)`, which have to sum to 0.
+    pub fn from_cube(cube: Vec3<i64>) -> Option<Self> {
+        if cube.x + cube.y + cube.z == 0 {
+            Some(Hex::new(cube.x, cube.y))
+        } else {
+            None
+        }
+    }
+
+    /// The cube coordinates `(q, r, s)`.
+    pub fn to_cube(self) -> Vec3<i64> {
+        Vec3::new(self.q, self.r, self.s())
+    }
+
+    /// The number of steps between the two hexagons.
+    pub fn distance(self, other: Hex) -> i64 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+    }
+}
+
+impl StepInDirection<HexDirection> for Hex {
+    #[inline]
+    fn step_checked(&self, direction: HexDirection) -> Option<Self> {
+        let (dq, dr) = direction.offset();
+        Some(Hex::new(self.q.checked_add(dq)?, self.r.checked_add(dr)?))
+    }
+}
+
+#[test]
+fn hex() {
+    use super::neighbors;
+    use crate::graph::bfs_path;
+    assert_eq!(HexDirection::NorthWest.clockwise(), HexDirection::North);
+    assert_eq!(HexDirection::SouthEast.reverse(), HexDirection::NorthWest);
+
+    // Going around a hexagon ends where it started
+    let origin = Hex::default();
+    let around = HexDirection::ALL
+        .iter()
+        .try_fold(origin, |hex, &dir| hex.step_checked(dir));
+    assert_eq!(around, Some(origin));
+    for (dir, next) in neighbors::<HexDirection, _>(origin) {
+        assert_eq!(next.distance(origin), 1);
+        assert_eq!(next.step_checked(dir.reverse()), Some(origin));
+    }
+
+    let target = Hex::new(3, -5);
+    assert_eq!(Hex::from_cube(target.to_cube()), Some(target));
+    assert_eq!(Hex::from_cube(Vec3::new(1, 1, 1)), None);
+    let path = bfs_path(
+        origin,
+        |&hex| neighbors::<HexDirection, _>(hex).map(|(_, next)| next),
+        |&hex| hex == target,
+    )
+    .unwrap();
+    assert_eq!(path.len() as i64 - 1, origin.distance(target));
+    assert_eq!(origin.distance(target), 5);
+}
@@ -1,7 +1,45 @@
 #![allow(dead_code)]
 use crate::vec2::Vec2;
 use num::{CheckedAdd, CheckedSub, One};
-use std::iter::TrustedLen;
+
+mod diagonal;
+mod hex;
+mod space;
+
+pub use diagonal::Direction8;
+pub use hex::{Hex, HexDirection};
+pub use space::Direction3;
+
+/// A set of directions in which a position on a grid can take a step, such as
+/// the four of `Direction` or the six of `HexDirection`.
+pub trait GridDirection: Copy + Eq + 'static {
+    /// Every direction of the set, each once.
+    const ALL: &'static [Self];
+
+    fn reverse(self) -> Self;
+
+    #[inline]
+    fn all() -> std::iter::Cloned<std::slice::Iter<'static, Self>> {
+        Self::ALL.iter().cloned()
+    }
+}
+
+/// A position that can take a step in any of the directions `D`.
+pub trait StepInDirection<D: GridDirection>: Sized {
+    /// The position a step away, or `None` if it can't be represented.
+    fn step_checked(&self, direction: D) -> Option<Self>;
+}
+
+/// The positions a step away from `pos` in every direction of `D`, along with
+/// their direction, which makes for the `next` function of the searches in
+/// `graph` on any kind of grid.
+pub fn neighbors<D, P>(pos: P) -> impl Iterator<Item = (D, P)> + Clone
+where
+    D: GridDirection,
+    P: StepInDirection<D> + Clone,
+{
+    D::all().filter_map(move |dir| pos.step_checked(dir).map(|next| (dir, next)))
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -13,15 +51,15 @@ pub enum Direction {
 
 impl Direction {
     #[inline]
-    pub fn vertical() -> impl Iterator<Item = Direction> + ExactSizeIterator + TrustedLen + Clone {
+    pub fn vertical() -> impl ExactSizeIterator<Item = Direction> + Clone {
         [Direction::North, Direction::South].iter().cloned()
     }
     #[inline]
-    pub fn horizontal() -> impl Iterator<Item = Direction> + ExactSizeIterator + TrustedLen + Clone {
+    pub fn horizontal() -> impl ExactSizeIterator<Item = Direction> + Clone {
         [Direction::West, Direction::East].iter().cloned()
     }
     #[inline]
-    pub fn each() -> impl Iterator<Item = Direction> + ExactSizeIterator + TrustedLen + Clone {
+    pub fn each() -> impl ExactSizeIterator<Item = Direction> + Clone {
         [
             Direction::North,
             Direction::South,
@@ -93,6 +131,20 @@ impl Direction {
     }
 }
 
+impl GridDirection for Direction {
+    const ALL: &'static [Direction] = &[
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    #[inline]
+    fn reverse(self) -> Direction {
+        Direction::reverse(self)
+    }
+}
+
 pub trait MoveInDirection: Sized {
     type Bound;
     type Number: Ord + One;
@@ -153,3 +205,13 @@ where
         Some(Vec2::new(x, y))
     }
 }
+
+impl<I> StepInDirection<Direction> for Vec2<I>
+where
+    I: Clone + Ord + One + CheckedAdd + CheckedSub,
+{
+    #[inline]
+    fn step_checked(&self, direction: Direction) -> Option<Self> {
+        self.step_in_direction_checked(direction)
+    }
+}
@@ -0,0 +1,108 @@
+use super::diagonal::offset_checked;
+use super::{Direction, GridDirection, StepInDirection};
+use crate::vec3::Vec3;
+use num::{CheckedAdd, CheckedSub, One};
+
+/// The six directions towards the faces of a cube on a 3D grid. They extend
+/// `Direction` on the `x` and `y` axes, with `Up` towards positive `z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction3 {
+    North,
+    South,
+    West,
+    East,
+    Up,
+    Down,
+}
+
+impl Direction3 {
+    /// The offset of a step in this direction, as `(x, y, z)`.
+    #[inline]
+    pub fn offset(self) -> (i8, i8, i8) {
+        use Direction3::*;
+        match self {
+            North => (0, -1, 0),
+            South => (0, 1, 0),
+            West => (-1, 0, 0),
+            East => (1, 0, 0),
+            Up => (0, 0, 1),
+            Down => (0, 0, -1),
+        }
+    }
+}
+
+impl GridDirection for Direction3 {
+    const ALL: &'static [Direction3] = &[
+        Direction3::North,
+        Direction3::South,
+        Direction3::West,
+        Direction3::East,
+        Direction3::Up,
+        Direction3::Down,
+    ];
+
+    #[inline]
+    fn reverse(self) -> Direction3 {
+        use Direction3::*;
+        match self {
+            North => South,
+            South => North,
+            West => East,
+            East => West,
+            Up => Down,
+            Down => Up,
+        }
+    }
+}
+
+impl From<Direction> for Direction3 {
+    fn from(direction: Direction) -> Direction3 {
+        match direction {
+            Direction::North => Direction3::North,
+            Direction::South => Direction3::South,
+            Direction::West => Direction3::West,
+            Direction::East => Direction3::East,
+        }
+    }
+}
+
+impl<I> StepInDirection<Direction3> for Vec3<I>
+where
+    I: Clone + One + CheckedAdd + CheckedSub,
+{
+    #[inline]
+    fn step_checked(&self, direction: Direction3) -> Option<Self> {
+        let (dx, dy, dz) = direction.offset();
+        Some(Vec3::new(
+            offset_checked(&self.x, dx)?,
+            offset_checked(&self.y, dy)?,
+            offset_checked(&self.z, dz)?,
+        ))
+    }
+}
+
+#[test]
+fn direction3() {
+    use super::neighbors;
+    use crate::graph::bfs_path;
+    use crate::vec3::Vec3i;
+    assert!(Direction3::ALL.iter().all(|dir| dir.reverse() != *dir));
+    let origin = Vec3i::new(0, 0, 0);
+    assert_eq!(neighbors::<Direction3, _>(origin).count(), 6);
+
+    // A wall at x = 1 with a single hole at (1, 2, -1)
+    let open = |pos: &Vec3i| pos.x != 1 || (pos.y == 2 && pos.z == -1);
+    let path = bfs_path(
+        origin,
+        |&pos| {
+            neighbors::<Direction3, _>(pos)
+                .map(|(_, next)| next)
+                .filter(|next| {
+                    open(next) && next.x.abs() < 4 && next.y.abs() < 4 && next.z.abs() < 4
+                })
+        },
+        |pos| *pos == Vec3i::new(2, 0, 0),
+    )
+    .unwrap();
+    assert_eq!(path.len() - 1, 8);
+}
@@ -22,6 +22,16 @@ pub enum AoCError {
     Logic(&'static str),
     #[error("incorrect input ({0})")]
     IncorrectInput(&'static str),
+    #[error("{source} at line {line}, column {column}")]
+    AtPosition {
+        line: usize,
+        column: usize,
+        source: Box<AoCError>,
+    },
+    #[error("panicked at {location}: {message}")]
+    Panic { message: String, location: String },
+    #[error("timed out after {0:?}")]
+    Timeout(std::time::Duration),
 }
 
 impl<E: Debug> From<nom::Err<E>> for AoCError {
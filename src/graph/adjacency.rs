@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+use super::Node;
+use crate::HashMap;
+
+/// A directed graph with weighted edges, stored as adjacency lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<N: Node, C> {
+    edges: HashMap<N, Vec<(N, C)>>,
+}
+
+impl<N: Node, C> Default for Graph<N, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Node, C> Graph<N, C> {
+    pub fn new() -> Self {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+
+    /// Adds a node without any edges. Returns false if it was already present.
+    pub fn add_node(&mut self, node: N) -> bool {
+        if self.edges.contains_key(&node) {
+            return false;
+        }
+        self.edges.insert(node, Vec::new());
+        true
+    }
+
+    /// Adds an edge from `from` to `to`, adding the nodes if they're new.
+    pub fn add_edge(&mut self, from: N, to: N, cost: C) {
+        self.add_node(to.clone());
+        self.edges.entry(from).or_default().push((to, cost));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: N, b: N, cost: C)
+    where
+        C: Clone,
+    {
+        self.add_edge(a.clone(), b.clone(), cost.clone());
+        self.add_edge(b, a, cost);
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.edges.contains_key(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    /// The outgoing edges of a node, empty if the node isn't in the graph.
+    pub fn edges(&self, node: &N) -> &[(N, C)] {
+        self.edges.get(node).map_or(&[], |edges| edges.as_slice())
+    }
+
+    /// The outgoing edges of a node, in the form taken by the `next` functions
+    /// of the searches, such as `dijkstra(start, |n| graph.neighbors(n))`.
+    pub fn neighbors(&self, node: &N) -> impl Iterator<Item = (N, C)> + '_
+    where
+        C: Clone,
+    {
+        self.edges(node).iter().cloned()
+    }
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+
+use super::{dijkstra, Cost, Node};
+use crate::HashMap;
+
+/// The shortest distance between each pair of nodes, indexed as `[from][to]`.
+/// Pairs without a path between them are left out.
+pub type AllPairs<N, C> = HashMap<N, HashMap<N, C>>;
+
+/// Finds the shortest distance between every pair of `nodes`. Edges to nodes
+/// that aren't listed are ignored. Runs in `O(n³)`, which suits small dense
+/// graphs, such as the ones created by contracting a grid.
+pub fn floyd_warshall<N, C, FN, NI>(nodes: &[N], mut next: FN) -> AllPairs<N, C>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = (N, C)>,
+{
+    let count = nodes.len();
+    let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let mut dist: Vec<Option<C>> = vec![None; count * count];
+    for (from, node) in nodes.iter().enumerate() {
+        dist[from * count + from] = Some(C::zero());
+        for (successor, cost) in next(node) {
+            if let Some(&to) = index.get(&successor) {
+                let slot = &mut dist[from * count + to];
+                if slot.as_ref().is_none_or(|existing| cost < *existing) {
+                    *slot = Some(cost);
+                }
+            }
+        }
+    }
+
+    for via in 0..count {
+        for from in 0..count {
+            let to_via = match &dist[from * count + via] {
+                Some(cost) => cost.clone(),
+                None => continue,
+            };
+            for to in 0..count {
+                let from_via = match &dist[via * count + to] {
+                    Some(cost) => to_via.clone() + cost.clone(),
+                    None => continue,
+                };
+                let slot = &mut dist[from * count + to];
+                if slot.as_ref().is_none_or(|existing| from_via < *existing) {
+                    *slot = Some(from_via);
+                }
+            }
+        }
+    }
+
+    let mut dist = dist.into_iter();
+    nodes
+        .iter()
+        .map(|from| {
+            let distances = nodes
+                .iter()
+                .zip(dist.by_ref().take(count))
+                .filter_map(|(to, cost)| cost.map(|cost| (to.clone(), cost)))
+                .collect();
+            (from.clone(), distances)
+        })
+        .collect()
+}
+
+/// Finds the shortest distance from each of `nodes` to every node reachable
+/// from it, by running Dijkstra from each of them. Suits sparse graphs, and
+/// unlike `floyd_warshall`, paths can run through nodes that aren't listed.
+pub fn all_pairs_dijkstra<N, C, FN, NI, I>(nodes: I, mut next: FN) -> AllPairs<N, C>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = (N, C)>,
+    I: IntoIterator<Item = N>,
+{
+    nodes
+        .into_iter()
+        .map(|from| {
+            let distances = dijkstra(from.clone(), &mut next);
+            (from, distances)
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use super::observe::{Event, Observer};
 use crate::HashMap;
 use num::traits::Zero;
 use std::cmp::{Ord, Ordering, PartialEq, PartialOrd};
@@ -7,14 +8,28 @@ use std::collections::BinaryHeap;
 use std::hash::Hash;
 use std::ops::Add;
 
-pub trait Node = Clone + Eq + Hash;
-pub trait Cost = Clone + Ord + Add + Zero;
+pub trait Node: Clone + Eq + Hash {}
+impl<T: Clone + Eq + Hash> Node for T {}
+pub trait Cost: Clone + Ord + Add<Output = Self> + Zero {}
+impl<T: Clone + Ord + Add<Output = T> + Zero> Cost for T {}
 
+/// The state of an A* search. It can be reused for multiple searches, which
+/// keeps the memory that was allocated by the earlier ones. After a search, the
+/// explored nodes and statistics stay available until the next one starts.
 #[derive(Debug, Clone)]
 pub struct AStar<N: Node, C: Cost> {
     meta: HashMap<N, Meta<N, C>>,
     open: BinaryHeap<Open<N, C>>,
     path: Vec<(N, C)>,
+    stats: Stats,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of nodes that were taken from the open set and expanded.
+    pub nodes_expanded: usize,
+    /// The largest size the open set reached.
+    pub max_open_size: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -25,11 +40,12 @@ struct Meta<N: Node, C: Cost> {
     parent: Option<N>,
 }
 
+/// An entry in an open set, ordered so that a `BinaryHeap` pops the lowest cost.
 #[derive(Debug, Clone, Eq)]
-struct Open<N: Node, C: Cost> {
-    cost: C,
-    node: N,
-    counter: usize,
+pub(super) struct Open<N: Node, C: Cost> {
+    pub(super) cost: C,
+    pub(super) node: N,
+    pub(super) counter: usize,
 }
 
 impl<N: Node, C: Cost> PartialEq for Open<N, C> {
@@ -52,36 +68,127 @@ impl<N: Node, C: Cost> Ord for Open<N, C> {
     }
 }
 
+impl<N: Node, C: Cost> Default for AStar<N, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<N: Node, C: Cost> AStar<N, C> {
     pub fn new() -> Self {
         AStar {
             meta: HashMap::new(),
             open: BinaryHeap::new(),
             path: Vec::new(),
+            stats: Stats::default(),
         }
     }
 
-    pub fn into_last_path(self) -> Vec<(N, C)> {
-        self.path
+    /// The path found by the last search, along with the cost to reach each
+    /// node on it. Empty if no path was found.
+    pub fn last_path(&self) -> std::slice::Iter<'_, (N, C)> {
+        self.path.iter()
+    }
+
+    pub fn into_last_path(self) -> std::vec::IntoIter<(N, C)> {
+        self.path.into_iter()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// The nodes that were expanded during the last search.
+    pub fn explored(&self) -> impl Iterator<Item = &N> {
+        self.meta
+            .iter()
+            .filter(|(_, meta)| meta.is_closed)
+            .map(|(node, _)| node)
     }
 
     pub fn solve<FN, FH, FD, NI>(
+        &mut self,
+        init: N,
+        next: FN,
+        heuristic: FH,
+        is_done: FD,
+    ) -> Option<&Vec<(N, C)>>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        self.solve_impl(init, next, heuristic, is_done, None, &mut ())
+    }
+
+    /// Like `solve`, but reports every node it opens and closes to `observer`,
+    /// and the path once it's found.
+    pub fn solve_observed<FN, FH, FD, NI, O>(
+        &mut self,
+        init: N,
+        next: FN,
+        heuristic: FH,
+        is_done: FD,
+        observer: &mut O,
+    ) -> Option<&Vec<(N, C)>>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+        O: Observer<N>,
+    {
+        self.solve_impl(init, next, heuristic, is_done, None, observer)
+    }
+
+    /// Like `solve`, but gives up on every route of which the estimated total
+    /// cost (the cost so far plus the heuristic) exceeds `bound`. With an
+    /// admissible heuristic, this finds no path if the shortest one costs more.
+    pub fn solve_bounded<FN, FH, FD, NI>(
+        &mut self,
+        init: N,
+        next: FN,
+        heuristic: FH,
+        is_done: FD,
+        bound: C,
+    ) -> Option<&Vec<(N, C)>>
+    where
+        FN: FnMut(&N) -> NI,
+        FH: FnMut(&N) -> C,
+        FD: FnMut(&N) -> bool,
+        NI: IntoIterator<Item = (N, C)>,
+    {
+        self.solve_impl(init, next, heuristic, is_done, Some(bound), &mut ())
+    }
+
+    fn solve_impl<FN, FH, FD, NI, O>(
         &mut self,
         init: N,
         mut next: FN,
         mut heuristic: FH,
         mut is_done: FD,
+        bound: Option<C>,
+        observer: &mut O,
     ) -> Option<&Vec<(N, C)>>
     where
         FN: FnMut(&N) -> NI,
         FH: FnMut(&N) -> C,
         FD: FnMut(&N) -> bool,
         NI: IntoIterator<Item = (N, C)>,
+        O: Observer<N>,
     {
+        let exceeds_bound = |cost: &C| bound.as_ref().is_some_and(|bound| cost > bound);
         // Used to get FIFO behaviour from the open set
         let mut counter = 0;
+        self.meta.clear();
+        self.open.clear();
         self.path.clear();
+        self.stats = Stats::default();
         let init_heuristic = heuristic(&init);
+        if exceeds_bound(&init_heuristic) {
+            return None;
+        }
         let init_meta = Meta {
             is_closed: false,
             path: C::zero(),
@@ -89,12 +196,17 @@ impl<N: Node, C: Cost> AStar<N, C> {
             parent: None,
         };
         self.meta.insert(init.clone(), init_meta);
+        observer.observe(Event::Opened {
+            node: &init,
+            from: None,
+        });
         let init_open = Open {
             node: init,
             cost: init_heuristic,
             counter,
         };
         self.open.push(init_open);
+        self.stats.max_open_size = 1;
 
         while let Some(open) = self.open.pop() {
             let meta = self.meta.get_mut(&open.node).unwrap();
@@ -105,20 +217,23 @@ impl<N: Node, C: Cost> AStar<N, C> {
                 continue;
             }
             meta.is_closed = true;
+            self.stats.nodes_expanded += 1;
+            observer.observe(Event::Closed(&open.node));
 
             if is_done(&open.node) {
                 // Reconstruct the path
                 let mut current_node = Some(&open.node);
                 while let Some(n) = current_node {
-                    let meta = &self.meta[&n];
+                    let meta = &self.meta[n];
                     self.path.push((n.clone(), meta.path.clone()));
                     current_node = meta.parent.as_ref();
                 }
 
                 self.path.reverse();
-
-                self.open.clear();
-                self.meta.clear();
+                if observer.is_enabled() {
+                    let nodes: Vec<N> = self.path.iter().map(|(n, _)| n.clone()).collect();
+                    observer.observe(Event::PathFound(&nodes));
+                }
                 return Some(&self.path);
             }
             let path_cost = meta.path.clone();
@@ -138,15 +253,23 @@ impl<N: Node, C: Cost> AStar<N, C> {
                         if meta.path <= path_cost {
                             continue;
                         }
+                        let cost = path_cost.clone() + meta.heuristic.clone();
+                        if exceeds_bound(&cost) {
+                            continue;
+                        }
                         // Update price
-                        meta.path = path_cost.clone();
+                        meta.path = path_cost;
                         meta.parent = Some(open.node.clone());
-                        path_cost
+                        cost
                     }
                     // New node
                     None => {
                         let path_cost = edge_cost + path_cost.clone();
                         let heuristic_cost = heuristic(&node);
+                        let cost = path_cost.clone() + heuristic_cost.clone();
+                        if exceeds_bound(&cost) {
+                            continue;
+                        }
                         self.meta.insert(
                             node.clone(),
                             Meta {
@@ -156,20 +279,23 @@ impl<N: Node, C: Cost> AStar<N, C> {
                                 parent: Some(open.node.clone()),
                             },
                         );
-                        path_cost + heuristic_cost
+                        cost
                     }
                 };
+                observer.observe(Event::Opened {
+                    node: &node,
+                    from: Some(&open.node),
+                });
                 counter += 1;
                 self.open.push(Open {
                     node,
                     cost,
                     counter,
                 });
+                self.stats.max_open_size = self.stats.max_open_size.max(self.open.len());
             }
         }
 
-        self.open.clear();
-        self.meta.clear();
         None
     }
 }
@@ -190,7 +316,7 @@ where
 {
     let mut state = AStar::new();
     if state.solve(init, next, heuristic, is_done).is_some() {
-        Some(state.into_last_path())
+        Some(state.into_last_path().collect())
     } else {
         None
     }
@@ -201,7 +327,7 @@ mod tests {
     use super::*;
     use crate::{mat2::Mat2, vec2::Vec2us};
     use arrayvec::ArrayVec;
-    const TEST_FILE: &'static str = include_str!("astar_tests.txt");
+    const TEST_FILE: &str = include_str!("astar_tests.txt");
 
     #[derive(Debug, Clone, PartialEq, Eq)]
     struct TestCase {
@@ -218,8 +344,7 @@ mod tests {
 
         fn maze_char1(s: &str) -> IResult<&str, &str> {
             let mut matched_count = 0;
-            let mut chars = s.chars();
-            while let Some(c) = chars.next() {
+            for c in s.chars() {
                 if c == '#' || c == '.' || c == 'S' || c == 'E' {
                     matched_count += 1;
                 } else {
@@ -346,4 +471,61 @@ mod tests {
             assert_eq!(path_length, found_path_length);
         }
     }
+
+    #[test]
+    fn reuse_stats_and_bound() {
+        let size = 5;
+        let next = |&(x, y): &(usize, usize)| {
+            let mut next = ArrayVec::<((usize, usize), usize), 2>::new();
+            if x + 1 < size {
+                next.push(((x + 1, y), 1));
+            }
+            if y + 1 < size {
+                next.push(((x, y + 1), 1));
+            }
+            next
+        };
+        let heuristic = |&(x, y): &(usize, usize)| (size - 1 - x) + (size - 1 - y);
+        let is_done = |&node: &(usize, usize)| node == (size - 1, size - 1);
+
+        let mut astar = AStar::new();
+        assert!(astar
+            .solve_bounded((0, 0), next, heuristic, is_done, 7)
+            .is_none());
+        assert_eq!(astar.last_path().count(), 0);
+        let path = astar
+            .solve_bounded((0, 0), next, heuristic, is_done, 8)
+            .unwrap();
+        assert_eq!(path.last().unwrap().1, 8);
+
+        let stats = astar.stats();
+        assert!(stats.nodes_expanded >= 9 && stats.max_open_size > 0);
+        assert!(astar.explored().any(|&node| node == (0, 0)));
+        astar.solve((0, 0), next, heuristic, is_done).unwrap();
+        assert_eq!(astar.stats(), stats);
+        let path: Vec<_> = astar.into_last_path().map(|(node, _)| node).collect();
+        assert_eq!((path[0], path[8]), ((0, 0), (4, 4)));
+    }
+
+    #[test]
+    fn reopen_keeps_heuristic() {
+        // X is reached directly, then through A and then through B, each time
+        // more cheaply. After it's reopened through A it has to stay behind B,
+        // or it's expanded with the cost through A.
+        let next = |&node: &char| match node {
+            'S' => vec![('X', 20), ('A', 1), ('B', 3)],
+            'A' => vec![('X', 5)],
+            'B' => vec![('X', 1)],
+            'X' => vec![('G', 10)],
+            _ => vec![],
+        };
+        let heuristic = |&node: &char| match node {
+            'B' => 8,
+            'X' => 10,
+            _ => 0,
+        };
+        let path = astar_once('S', next, heuristic, |&node| node == 'G').unwrap();
+        let nodes: String = path.iter().map(|(node, _)| node).collect();
+        assert_eq!((nodes.as_str(), path.last().unwrap().1), ("SBXG", 14));
+    }
 }
@@ -1,12 +1,14 @@
 #![allow(dead_code)]
+use super::observe::{Event, Observer};
+use super::visited::Visited;
 use crate::HashMap;
-use std::collections::{hash_map::Entry, VecDeque};
+use std::collections::VecDeque;
 use std::hash::Hash;
 
 #[inline(always)]
 pub fn bfs<N, FN, FD, NI>(start: N, mut next: FN, mut done: FD) -> bool
 where
-    N: Hash + Eq,
+    N: Hash + Eq + Clone,
     FN: FnMut(&N) -> NI,
     FD: FnMut(&N) -> bool,
     NI: IntoIterator<Item = N>,
@@ -18,36 +20,238 @@ where
     )
 }
 
-pub fn bfs_meta<N, M, FN, FD, NI>(start: (N, M), mut next: FN, mut done: FD) -> bool
+pub fn bfs_meta<N, M, FN, FD, NI>(start: (N, M), next: FN, done: FD) -> bool
 where
-    N: Hash + Eq,
+    N: Hash + Eq + Clone,
     FN: FnMut(&N, &M) -> NI,
     FD: FnMut(&N, &M) -> bool,
     NI: IntoIterator<Item = (N, M)>,
 {
-    let mut visited = HashMap::new();
+    bfs_impl(&mut Visited::new(), start, next, done, &mut ())
+}
+
+/// Nodes are marked as visited when they're queued, so every node is opened
+/// once and keeps the meta of the first route that reached it.
+fn bfs_impl<N, M, FN, FD, NI, O>(
+    visited: &mut Visited<N, M>,
+    start: (N, M),
+    mut next: FN,
+    mut done: FD,
+    observer: &mut O,
+) -> bool
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N, &M) -> NI,
+    FD: FnMut(&N, &M) -> bool,
+    NI: IntoIterator<Item = (N, M)>,
+    O: Observer<N>,
+{
     let mut queue = VecDeque::new();
-    let mut current = start;
-    loop {
-        // Insert item if new
-        let entry = visited.entry(current.0);
-        if let Entry::Occupied(_) = entry {
-        } else {
-            let entry = entry.insert_entry(current.1);
-            let node = entry.key();
-            let meta = entry.get();
-            if done(node, meta) {
-                return true;
+    let (start, meta) = start;
+    if visited.insert(start.clone(), meta).is_some() {
+        observer.observe(Event::Opened {
+            node: &start,
+            from: None,
+        });
+        queue.push_back(start);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        observer.observe(Event::Closed(&node));
+        let meta = visited.get(&node).unwrap();
+        if done(&node, meta) {
+            return true;
+        }
+        for (successor, meta) in next(&node, meta) {
+            // Insert item if new
+            if visited.insert(successor.clone(), meta).is_some() {
+                observer.observe(Event::Opened {
+                    node: &successor,
+                    from: Some(&node),
+                });
+                queue.push_back(successor);
             }
-            queue.extend(next(node, meta));
         }
+    }
+    false
+}
 
-        // Next item
-        if let Some(next) = queue.pop_front() {
-            current = next;
-        } else {
-            break;
+/// Like `bfs`, but reports every node it opens and closes to `observer`, and
+/// the path to the node for which `done` returns true.
+pub fn bfs_observed<N, FN, FD, NI, O>(start: N, next: FN, done: FD, observer: &mut O) -> bool
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> NI,
+    FD: FnMut(&N) -> bool,
+    NI: IntoIterator<Item = N>,
+    O: Observer<N>,
+{
+    match bfs_path_impl(start, next, done, observer) {
+        Some(path) => {
+            observer.observe(Event::PathFound(&path));
+            true
         }
+        None => false,
     }
-    false
+}
+
+/// Finds the shortest path from `start` to the first node for which `done`
+/// returns true. The path includes both `start` and that node.
+pub fn bfs_path<N, FN, FD, NI>(start: N, next: FN, done: FD) -> Option<Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> NI,
+    FD: FnMut(&N) -> bool,
+    NI: IntoIterator<Item = N>,
+{
+    bfs_path_impl(start, next, done, &mut ())
+}
+
+fn bfs_path_impl<N, FN, FD, NI, O>(
+    start: N,
+    mut next: FN,
+    mut done: FD,
+    observer: &mut O,
+) -> Option<Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> NI,
+    FD: FnMut(&N) -> bool,
+    NI: IntoIterator<Item = N>,
+    O: Observer<N>,
+{
+    // Each node is stored along with its parent
+    let mut visited = Visited::new();
+    let mut goal = None;
+    bfs_impl(
+        &mut visited,
+        (start, None),
+        |node, _| {
+            let parent = node.clone();
+            next(node)
+                .into_iter()
+                .map(move |n| (n, Some(parent.clone())))
+        },
+        |node, _| {
+            if done(node) {
+                goal = Some(node.clone());
+                true
+            } else {
+                false
+            }
+        },
+        observer,
+    );
+
+    let mut path = vec![goal?];
+    while let Some(Some(parent)) = visited.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Finds the number of steps from `start` to every node reachable from it.
+pub fn bfs_distances<N, FN, NI>(start: N, mut next: FN) -> HashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    bfs_impl(
+        &mut visited,
+        (start, 0),
+        |node, &dist| next(node).into_iter().map(move |n| (n, dist + 1)),
+        |_, _| false,
+        &mut (),
+    );
+    visited.into_map()
+}
+
+/// Iterates over the nodes reachable from `start`, grouped by the number of
+/// steps it takes to reach them. The first layer only contains `start`.
+pub fn bfs_layers<N, FN, NI>(start: N, next: FN) -> BfsLayers<N, FN>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    visited.insert(start.clone(), ());
+    BfsLayers {
+        visited,
+        layer: vec![start],
+        next,
+    }
+}
+
+pub struct BfsLayers<N, FN> {
+    visited: Visited<N, ()>,
+    layer: Vec<N>,
+    next: FN,
+}
+
+impl<N, FN, NI> Iterator for BfsLayers<N, FN>
+where
+    N: Hash + Eq + Clone,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+{
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Vec<N>> {
+        if self.layer.is_empty() {
+            return None;
+        }
+        let mut next_layer = Vec::new();
+        for node in &self.layer {
+            for successor in (self.next)(node) {
+                if !self.visited.contains(&successor) {
+                    self.visited.insert(successor.clone(), ());
+                    next_layer.push(successor);
+                }
+            }
+        }
+        Some(std::mem::replace(&mut self.layer, next_layer))
+    }
+}
+
+#[test]
+fn bfs_variants() {
+    // A 3x3 grid without its center
+    let next = |&(x, y): &(i32, i32)| {
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .iter()
+            .cloned()
+            .filter(|&(x, y)| (0..3).contains(&x) && (0..3).contains(&y) && (x, y) != (1, 1))
+            .collect::<Vec<_>>()
+    };
+
+    let path = bfs_path((0, 0), next, |&node| node == (2, 2)).unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!((path[0], path[4]), ((0, 0), (2, 2)));
+    assert!(path.windows(2).all(|w| next(&w[0]).contains(&w[1])));
+    assert_eq!(bfs_path((0, 0), next, |&node| node == (1, 1)), None);
+
+    let distances = bfs_distances((0, 0), next);
+    assert_eq!(distances.len(), 8);
+    assert_eq!((distances[&(2, 0)], distances[&(2, 2)]), (2, 4));
+
+    let layers: Vec<usize> = bfs_layers((0, 0), next).map(|layer| layer.len()).collect();
+    assert_eq!(layers, [1, 2, 2, 2, 1]);
+
+    // (2, 2) is reached from both (2, 1) and (1, 2), but only opened once
+    let mut opened = Vec::new();
+    let mut closed = 0;
+    let mut observer = |event: Event<'_, (i32, i32)>| match event {
+        Event::Opened { node, .. } => opened.push(*node),
+        Event::Closed(_) => closed += 1,
+        Event::PathFound(_) => {}
+    };
+    assert!(!bfs_observed((0, 0), next, |_| false, &mut observer));
+    assert_eq!((opened.len(), closed), (8, 8));
+    opened.sort_unstable();
+    opened.dedup();
+    assert_eq!(opened.len(), 8);
 }
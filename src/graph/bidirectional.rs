@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use super::Node;
+use crate::HashMap;
+
+/// Finds the number of steps on the shortest path from `start` to `goal`, by
+/// searching from both ends at once. Every edge has to work in both
+/// directions, since `next` is also used to step away from the goal.
+pub fn bidirectional_bfs<N, FN, NI>(start: N, goal: N, mut next: FN) -> Option<usize>
+where
+    N: Node,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(0);
+    }
+    let mut forward = HashMap::new();
+    let mut backward = HashMap::new();
+    forward.insert(start.clone(), 0);
+    backward.insert(goal.clone(), 0);
+    let mut forward_layer = vec![start];
+    let mut backward_layer = vec![goal];
+
+    while !forward_layer.is_empty() && !backward_layer.is_empty() {
+        // Grow the side with the smallest frontier by an entire layer. The
+        // first layer that meets the other side contains the shortest path,
+        // but not necessarily through the first meeting point.
+        let (layer, seen, other) = if forward_layer.len() <= backward_layer.len() {
+            (&mut forward_layer, &mut forward, &backward)
+        } else {
+            (&mut backward_layer, &mut backward, &forward)
+        };
+        let mut shortest: Option<usize> = None;
+        let mut next_layer = Vec::new();
+        for node in layer.drain(..) {
+            let dist = seen[&node] + 1;
+            for successor in next(&node) {
+                if seen.contains_key(&successor) {
+                    continue;
+                }
+                if let Some(other_dist) = other.get(&successor) {
+                    let total = dist + other_dist;
+                    shortest = Some(shortest.map_or(total, |shortest| shortest.min(total)));
+                }
+                seen.insert(successor.clone(), dist);
+                next_layer.push(successor);
+            }
+        }
+        if shortest.is_some() {
+            return shortest;
+        }
+        *layer = next_layer;
+    }
+
+    None
+}
@@ -0,0 +1,61 @@
+#![allow(dead_code)]
+
+use super::astar::Open;
+use super::{Cost, Node};
+use crate::HashMap;
+use std::collections::{hash_map::Entry, BinaryHeap};
+
+/// Finds the shortest distance from `start` to every node reachable from it.
+pub fn dijkstra<N, C, FN, NI>(start: N, mut next: FN) -> HashMap<N, C>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = HashMap::new();
+    let mut best = HashMap::new();
+    let mut open = BinaryHeap::new();
+    let mut counter = 0;
+    best.insert(start.clone(), C::zero());
+    open.push(Open {
+        cost: C::zero(),
+        node: start,
+        counter,
+    });
+
+    while let Some(Open { cost, node, .. }) = open.pop() {
+        let entry = match distances.entry(node) {
+            Entry::Occupied(_) => continue,
+            Entry::Vacant(entry) => entry,
+        };
+        let node = entry.key().clone();
+        entry.insert(cost.clone());
+
+        for (successor, edge_cost) in next(&node) {
+            if distances.contains_key(&successor) {
+                continue;
+            }
+            let successor_cost = cost.clone() + edge_cost;
+            match best.entry(successor.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if *entry.get() <= successor_cost {
+                        continue;
+                    }
+                    entry.insert(successor_cost.clone());
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(successor_cost.clone());
+                }
+            }
+            counter += 1;
+            open.push(Open {
+                cost: successor_cost,
+                node: successor,
+                counter,
+            });
+        }
+    }
+
+    distances
+}
@@ -0,0 +1,230 @@
+#![allow(dead_code)]
+
+use super::observe::{Event, Observer};
+use crate::mat2::Mat2;
+use crate::vec2::Vec2us;
+use crate::HashMap;
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+/// An owned copy of an `Event`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recorded<N> {
+    Opened { node: N, from: Option<N> },
+    Closed(N),
+    PathFound(Vec<N>),
+}
+
+/// An observer that keeps every event, so the search can be exported after it
+/// finished.
+#[derive(Debug, Clone)]
+pub struct Recorder<N> {
+    events: Vec<Recorded<N>>,
+}
+
+impl<N> Default for Recorder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone> Observer<N> for Recorder<N> {
+    fn observe(&mut self, event: Event<'_, N>) {
+        self.events.push(match event {
+            Event::Opened { node, from } => Recorded::Opened {
+                node: node.clone(),
+                from: from.cloned(),
+            },
+            Event::Closed(node) => Recorded::Closed(node.clone()),
+            Event::PathFound(path) => Recorded::PathFound(path.to_vec()),
+        });
+    }
+}
+
+impl<N> Recorder<N> {
+    pub fn new() -> Self {
+        Recorder { events: Vec::new() }
+    }
+
+    pub fn events(&self) -> &[Recorded<N>] {
+        &self.events
+    }
+
+    /// The nodes in the order they were closed.
+    pub fn closed(&self) -> impl Iterator<Item = &N> {
+        self.events.iter().filter_map(|event| match event {
+            Recorded::Closed(node) => Some(node),
+            _ => None,
+        })
+    }
+
+    pub fn path(&self) -> Option<&[N]> {
+        self.events.iter().rev().find_map(|event| match event {
+            Recorded::PathFound(path) => Some(&path[..]),
+            _ => None,
+        })
+    }
+}
+
+impl Recorder<Vec2us> {
+    /// Renders the search on top of the grid, with a frame after every `step`
+    /// closed nodes and one at the end. Opened nodes are drawn as `o`, closed
+    /// ones as `x` and the path as `*`, every other cell is drawn by `cell`.
+    pub fn ascii_frames<T, F>(&self, grid: &Mat2<T>, step: usize, mut cell: F) -> Vec<String>
+    where
+        T: Clone,
+        F: FnMut(&T) -> char,
+    {
+        let mut canvas = Mat2::new(' ', grid.size());
+        for (pos, item) in grid.iter() {
+            canvas[pos] = cell(item);
+        }
+        let render = |canvas: &Mat2<char>| {
+            let mut frame = canvas.render(|&c| c);
+            frame.pop();
+            frame
+        };
+
+        let step = step.max(1);
+        let mut frames = Vec::new();
+        let mut closed = 0;
+        for event in &self.events {
+            match event {
+                Recorded::Opened { node, .. } => {
+                    if canvas[*node] != 'x' {
+                        canvas[*node] = 'o';
+                    }
+                }
+                Recorded::Closed(node) => {
+                    canvas[*node] = 'x';
+                    closed += 1;
+                    if closed % step == 0 {
+                        frames.push(render(&canvas));
+                    }
+                }
+                Recorded::PathFound(path) => {
+                    for node in path {
+                        canvas[*node] = '*';
+                    }
+                }
+            }
+        }
+        if closed % step != 0 || self.path().is_some() || frames.is_empty() {
+            frames.push(render(&canvas));
+        }
+        frames
+    }
+}
+
+impl<N: Debug + Eq + Hash> Recorder<N> {
+    /// Exports the search tree as a Graphviz DOT graph. Every node is labeled
+    /// with the order it was closed in, and the edges along the path are red.
+    pub fn to_dot(&self) -> String {
+        let mut ids = HashMap::new();
+        let mut order = HashMap::new();
+        let mut edges = Vec::new();
+        for event in &self.events {
+            match event {
+                Recorded::Opened { node, from } => {
+                    let next_id = ids.len();
+                    ids.entry(node).or_insert(next_id);
+                    if let Some(from) = from {
+                        edges.push((from, node));
+                    }
+                }
+                Recorded::Closed(node) => {
+                    let next_order = order.len();
+                    order.entry(node).or_insert(next_order);
+                }
+                Recorded::PathFound(_) => {}
+            }
+        }
+        let on_path: Vec<(&N, &N)> = self
+            .path()
+            .map(|path| path.windows(2).map(|w| (&w[0], &w[1])).collect())
+            .unwrap_or_default();
+
+        let mut nodes: Vec<_> = ids.iter().map(|(&node, &id)| (id, node)).collect();
+        nodes.sort_unstable_by_key(|(id, _)| *id);
+        let mut dot = String::from("digraph search {\n");
+        for (id, node) in nodes {
+            let label = format!("{:?}", node).replace('"', "\\\"");
+            match order.get(node) {
+                Some(closed) => writeln!(dot, "    n{} [label=\"{}\\n#{}\"];", id, label, closed),
+                None => writeln!(dot, "    n{} [label=\"{}\", style=dashed];", id, label),
+            }
+            .unwrap();
+        }
+        for (from, to) in edges {
+            let (from_id, to_id) = (ids[from], ids[to]);
+            if on_path.contains(&(from, to)) {
+                writeln!(dot, "    n{} -> n{} [color=red];", from_id, to_id)
+            } else {
+                writeln!(dot, "    n{} -> n{};", from_id, to_id)
+            }
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[test]
+fn export_searches() {
+    use super::{bfs_observed, flood_observed, AStar};
+    let rows = ["..#", ".##", "..."];
+    let mut grid = Mat2::new(false, Vec2us::new(3, 3));
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            grid[Vec2us::new(x, y)] = c == '.';
+        }
+    }
+    let next = |pos: &Vec2us| {
+        let mut next = Vec::new();
+        if pos.x > 0 {
+            next.push(Vec2us::new(pos.x - 1, pos.y));
+        }
+        if pos.x < 2 {
+            next.push(Vec2us::new(pos.x + 1, pos.y));
+        }
+        if pos.y > 0 {
+            next.push(Vec2us::new(pos.x, pos.y - 1));
+        }
+        if pos.y < 2 {
+            next.push(Vec2us::new(pos.x, pos.y + 1));
+        }
+        next.retain(|pos| grid[*pos]);
+        next
+    };
+    let goal = Vec2us::new(2, 2);
+
+    let mut recorder = Recorder::new();
+    assert!(bfs_observed(
+        Vec2us::new(0, 0),
+        next,
+        |&pos| pos == goal,
+        &mut recorder
+    ));
+    assert_eq!(recorder.closed().count(), 6);
+    assert_eq!(recorder.path().map(|path| path.len()), Some(5));
+    let frames = recorder.ascii_frames(&grid, 3, |&open| if open { '.' } else { '#' });
+    assert_eq!(frames, ["xx#\nx##\n...", "xx#\nx##\nxxx", "*x#\n*##\n***"]);
+
+    let mut astar = Recorder::new();
+    AStar::new().solve_observed(
+        Vec2us::new(0, 0),
+        |pos| next(pos).into_iter().map(|pos| (pos, 1)),
+        |pos| (2 - pos.x) + (2 - pos.y),
+        |&pos| pos == goal,
+        &mut astar,
+    );
+    assert_eq!(astar.path(), recorder.path());
+    assert_eq!(astar.closed().last(), Some(&goal));
+
+    let mut flooded = Recorder::new();
+    flood_observed(Vec2us::new(0, 0), next, &mut flooded);
+    assert_eq!(flooded.closed().count(), 6);
+    let dot = flooded.to_dot();
+    assert!(dot.starts_with("digraph search {\n    n0 [label=\"Vec2"));
+    assert_eq!(dot.matches("->").count(), 5);
+}
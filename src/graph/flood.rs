@@ -1,32 +1,71 @@
 #![allow(dead_code)]
-use crate::HashMap;
-use std::collections::hash_map::Entry;
+use super::observe::{Event, Observer};
+use super::visited::Visited;
+use crate::HashSet;
 use std::hash::Hash;
 
 #[inline(always)]
 pub fn flood<N, FN, NI>(start: N, mut next: FN)
 where
-    N: Eq + Hash,
+    N: Eq + Hash + Clone,
     FN: FnMut(&N) -> NI,
     NI: IntoIterator<Item = N>,
 {
-    let mut visited = HashMap::new();
-    flood_impl(&mut visited, start, &mut next);
+    let mut visited = Visited::new();
+    flood_impl(&mut visited, start, None, &mut next, &mut ());
 }
 
-fn flood_impl<N, FN, NI>(visited: &mut HashMap<N, ()>, current: N, next: &mut FN)
+/// Like `flood`, but returns every node that was reached.
+pub fn flood_reached<N, FN, NI>(start: N, mut next: FN) -> HashSet<N>
 where
-    N: Eq + Hash,
+    N: Eq + Hash + Clone,
     FN: FnMut(&N) -> NI,
     NI: IntoIterator<Item = N>,
 {
-    let entry = match visited.entry(current) {
-        Entry::Occupied(_) => return,
-        Entry::Vacant(e) => e.insert_entry(()),
-    };
+    let mut visited = Visited::new();
+    flood_impl(&mut visited, start, None, &mut next, &mut ());
+    visited
+        .into_map()
+        .into_iter()
+        .map(|(node, ())| node)
+        .collect()
+}
+
+/// Like `flood`, but reports every node to `observer`. A node is opened when
+/// it's reached, and closed once everything reachable through it was flooded.
+pub fn flood_observed<N, FN, NI, O>(start: N, mut next: FN, observer: &mut O)
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+    O: Observer<N>,
+{
+    let mut visited = Visited::new();
+    flood_impl(&mut visited, start, None, &mut next, observer);
+}
 
-    let current = entry.key();
-    for successor in next(current) {
-        flood_impl(visited, successor, next);
+fn flood_impl<N, FN, NI, O>(
+    visited: &mut Visited<N, ()>,
+    current: N,
+    from: Option<&N>,
+    next: &mut FN,
+    observer: &mut O,
+) where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+    O: Observer<N>,
+{
+    let current = match visited.insert(current, ()) {
+        Some(entry) => entry.key().clone(),
+        None => return,
+    };
+    observer.observe(Event::Opened {
+        node: &current,
+        from,
+    });
+    for successor in next(&current) {
+        flood_impl(visited, successor, Some(&current), next, observer);
     }
+    observer.observe(Event::Closed(&current));
 }
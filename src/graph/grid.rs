@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use super::{bfs_layers, Graph};
+use crate::mat2::Mat2;
+use crate::vec2::Vec2us;
+use arrayvec::ArrayVec;
+
+/// Turns a grid maze into a graph of only its interesting cells, such as keys
+/// or portals. Two interesting cells are connected when there's a walk between
+/// them that doesn't pass through any other interesting cell, weighted by the
+/// number of steps of the shortest such walk.
+pub fn contract_grid<T, FW, FI>(
+    grid: &Mat2<T>,
+    mut walkable: FW,
+    mut interesting: FI,
+) -> Graph<Vec2us, usize>
+where
+    T: Clone,
+    FW: FnMut(&T) -> bool,
+    FI: FnMut(&T) -> bool,
+{
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Kind {
+        Blocked,
+        Walkable,
+        Interesting,
+    }
+    let mut kinds = Mat2::new(Kind::Blocked, grid.size());
+    let mut points = Vec::new();
+    for (pos, cell) in grid.iter() {
+        if !walkable(cell) {
+            continue;
+        }
+        kinds[pos] = if interesting(cell) {
+            points.push(pos);
+            Kind::Interesting
+        } else {
+            Kind::Walkable
+        };
+    }
+
+    let mut graph = Graph::new();
+    for &start in &points {
+        graph.add_node(start);
+        let next = |&pos: &Vec2us| {
+            let mut next = ArrayVec::<Vec2us, 4>::new();
+            // Walks end at the first interesting cell they reach
+            if pos != start && kinds[pos] == Kind::Interesting {
+                return next;
+            }
+            next.extend(
+                kinds
+                    .neighbors4(pos)
+                    .filter(|&(_, _, &kind)| kind != Kind::Blocked)
+                    .map(|(_, pos, _)| pos),
+            );
+            next
+        };
+        for (steps, layer) in bfs_layers(start, next).enumerate().skip(1) {
+            for pos in layer {
+                if kinds[pos] == Kind::Interesting {
+                    graph.add_edge(start, pos, steps);
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+#[test]
+fn contract() {
+    let rows = ["a.b..", "#.#.#", "c...d"];
+    let mut grid = Mat2::new(b'#', Vec2us::new(5, 3));
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.bytes().enumerate() {
+            grid[Vec2us::new(x, y)] = c;
+        }
+    }
+    let graph = contract_grid(&grid, |&c| c != b'#', |&c| c.is_ascii_lowercase());
+    let edges = |x, y| {
+        let mut edges: Vec<_> = graph
+            .neighbors(&Vec2us::new(x, y))
+            .map(|(pos, steps)| (grid[pos], steps))
+            .collect();
+        edges.sort_unstable();
+        edges
+    };
+
+    assert_eq!(graph.len(), 4);
+    // `a` only reaches `d` by walking along the bottom, since `b` ends walks
+    assert_eq!(edges(0, 0), [(b'b', 2), (b'c', 4), (b'd', 6)]);
+    assert_eq!(edges(4, 2), [(b'a', 6), (b'b', 4), (b'c', 4)]);
+}
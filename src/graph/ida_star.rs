@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use super::{Cost, Node};
+
+/// Iterative deepening A*, which only keeps the current path in memory instead
+/// of every node it has seen. Nodes can be visited many times over, so it's
+/// only worth it when `AStar` would run out of memory. The result is in the
+/// same form as `AStar::solve`, and has the same cost when the heuristic is
+/// admissible.
+pub fn ida_star<N, C, FN, FH, FD, NI>(
+    init: N,
+    mut next: FN,
+    mut heuristic: FH,
+    mut is_done: FD,
+) -> Option<Vec<(N, C)>>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> NI,
+    FH: FnMut(&N) -> C,
+    FD: FnMut(&N) -> bool,
+    NI: IntoIterator<Item = (N, C)>,
+{
+    if is_done(&init) {
+        return Some(vec![(init, C::zero())]);
+    }
+
+    let mut bound = heuristic(&init);
+    loop {
+        // The lowest estimate that exceeded the bound, which becomes the next one
+        let mut next_bound: Option<C> = None;
+        let mut path = vec![(init.clone(), C::zero())];
+        let mut successors = vec![next(&init).into_iter()];
+
+        while let Some(iter) = successors.last_mut() {
+            let (node, edge_cost) = match iter.next() {
+                Some(successor) => successor,
+                None => {
+                    successors.pop();
+                    path.pop();
+                    continue;
+                }
+            };
+            // Walking in circles never leads to a shorter path
+            if path.iter().any(|(n, _)| *n == node) {
+                continue;
+            }
+            let cost = path.last().unwrap().1.clone() + edge_cost;
+            let estimate = cost.clone() + heuristic(&node);
+            if estimate > bound {
+                if next_bound.as_ref().is_none_or(|b| estimate < *b) {
+                    next_bound = Some(estimate);
+                }
+                continue;
+            }
+            if is_done(&node) {
+                path.push((node, cost));
+                return Some(path);
+            }
+            successors.push(next(&node).into_iter());
+            path.push((node, cost));
+        }
+
+        bound = next_bound?;
+    }
+}
+
+#[test]
+fn matches_astar() {
+    use super::astar_once;
+    // A 4x4 grid with a wall along x = 2 that has a gap at y = 3
+    let next = |&(x, y): &(i32, i32)| {
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .iter()
+            .cloned()
+            .filter(|&(x, y)| (0..4).contains(&x) && (0..4).contains(&y) && (x != 2 || y == 3))
+            .map(|pos| (pos, 1))
+            .collect::<Vec<_>>()
+    };
+    let heuristic = |&(x, y): &(i32, i32)| (3 - x).abs() + y.abs();
+    let is_done = |&pos: &(i32, i32)| pos == (3, 0);
+
+    let path = ida_star((0, 0), next, heuristic, is_done).unwrap();
+    let expected = astar_once((0, 0), next, heuristic, is_done).unwrap();
+    assert_eq!(path.last(), expected.last());
+    assert_eq!(path.last().unwrap().1, 9);
+    assert_eq!(path.len(), 10);
+
+    // Every path around a cycle is cut short once it gets back to its start
+    let cycle = |&node: &u32| vec![((node + 1) % 3, 1)];
+    assert_eq!(ida_star(0, cycle, |_| 0, |&node| node == 3), None);
+}
@@ -0,0 +1,151 @@
+#![allow(dead_code)]
+
+use super::Node;
+use crate::HashMap;
+
+/// A rooted tree that answers lowest common ancestor queries in logarithmic
+/// time, using a table of each node's 2^k-th ancestors.
+#[derive(Debug, Clone)]
+pub struct LcaTree<N: Node> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    depths: Vec<usize>,
+    /// `ancestors[k][idx]` is the 2^k-th ancestor of the node at `idx`, or the
+    /// root if the node isn't that deep.
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl<N: Node> LcaTree<N> {
+    /// Builds the tree of every node reachable from `root`. The children must
+    /// form a tree, a node reached twice only keeps its first parent.
+    pub fn new<FN, NI>(root: N, mut children: FN) -> Self
+    where
+        FN: FnMut(&N) -> NI,
+        NI: IntoIterator<Item = N>,
+    {
+        let mut nodes = vec![root.clone()];
+        let mut indices = HashMap::new();
+        indices.insert(root, 0);
+        let mut depths = vec![0];
+        let mut parents = vec![0];
+
+        let mut idx = 0;
+        while idx < nodes.len() {
+            for child in children(&nodes[idx]) {
+                if indices.contains_key(&child) {
+                    continue;
+                }
+                indices.insert(child.clone(), nodes.len());
+                nodes.push(child);
+                depths.push(depths[idx] + 1);
+                parents.push(idx);
+            }
+            idx += 1;
+        }
+
+        let max_depth = depths.iter().cloned().max().unwrap_or(0);
+        let mut ancestors = vec![parents];
+        while 1 << ancestors.len() <= max_depth {
+            let last = ancestors.last().unwrap();
+            let next = last.iter().map(|&ancestor| last[ancestor]).collect();
+            ancestors.push(next);
+        }
+
+        LcaTree {
+            nodes,
+            indices,
+            depths,
+            ancestors,
+        }
+    }
+
+    pub fn root(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Every node along with its depth, starting with the root at depth 0.
+    pub fn depths(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        self.nodes.iter().zip(self.depths.iter().cloned())
+    }
+
+    pub fn depth(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).map(|&idx| self.depths[idx])
+    }
+
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        let idx = *self.indices.get(node)?;
+        if idx == 0 {
+            None
+        } else {
+            Some(&self.nodes[self.ancestors[0][idx]])
+        }
+    }
+
+    fn ancestor_at_depth(&self, mut idx: usize, depth: usize) -> usize {
+        let mut climb = self.depths[idx] - depth;
+        let mut k = 0;
+        while climb > 0 {
+            if climb & 1 == 1 {
+                idx = self.ancestors[k][idx];
+            }
+            climb >>= 1;
+            k += 1;
+        }
+        idx
+    }
+
+    /// The deepest node that has both `a` and `b` in its subtree. A node is in
+    /// its own subtree.
+    pub fn lca(&self, a: &N, b: &N) -> Option<&N> {
+        let (a, b) = (*self.indices.get(a)?, *self.indices.get(b)?);
+        let depth = self.depths[a].min(self.depths[b]);
+        let (mut a, mut b) = (
+            self.ancestor_at_depth(a, depth),
+            self.ancestor_at_depth(b, depth),
+        );
+        if a == b {
+            return Some(&self.nodes[a]);
+        }
+        for level in self.ancestors.iter().rev() {
+            if level[a] != level[b] {
+                a = level[a];
+                b = level[b];
+            }
+        }
+        Some(&self.nodes[self.ancestors[0][a]])
+    }
+
+    /// The number of edges on the path between `a` and `b`.
+    pub fn distance(&self, a: &N, b: &N) -> Option<usize> {
+        let lca = self.depth(self.lca(a, b)?)?;
+        Some(self.depth(a)? + self.depth(b)? - 2 * lca)
+    }
+}
+
+#[test]
+fn ancestors() {
+    // 0 has the children 1 and 2, 1 has 3, and 3 has 4 and 5
+    let children = |&node: &u32| match node {
+        0 => vec![1, 2],
+        1 => vec![3],
+        3 => vec![4, 5],
+        _ => vec![],
+    };
+    let tree = LcaTree::new(0, children);
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree.depth(&5), Some(3));
+    assert_eq!(tree.parent(&4), Some(&3));
+    assert_eq!(tree.lca(&4, &5), Some(&3));
+    assert_eq!(tree.lca(&4, &1), Some(&1));
+    assert_eq!(tree.lca(&5, &2), Some(&0));
+    assert_eq!(tree.distance(&5, &2), Some(4));
+    assert_eq!(tree.lca(&4, &7), None);
+}
@@ -1,9 +1,36 @@
+mod adjacency;
+mod all_pairs;
 mod astar;
+mod bfs;
+mod bidirectional;
 mod dfs;
+mod dijkstra;
+mod export;
 mod flood;
-mod bfs;
+mod grid;
+mod ida_star;
+mod lca;
+mod mst;
+mod observe;
+mod parallel;
+mod scc;
+mod topo;
+mod visited;
 
-pub use astar::{astar_once, AStar};
+pub use adjacency::Graph;
+pub use all_pairs::{all_pairs_dijkstra, floyd_warshall, AllPairs};
+pub use astar::{astar_once, AStar, Cost, Node, Stats};
+pub use bfs::{bfs, bfs_distances, bfs_layers, bfs_meta, bfs_observed, bfs_path, BfsLayers};
+pub use bidirectional::bidirectional_bfs;
 pub use dfs::dfs;
-pub use flood::flood;
-pub use bfs::{bfs, bfs_meta};
\ No newline at end of file
+pub use dijkstra::dijkstra;
+pub use export::{Recorded, Recorder};
+pub use flood::{flood, flood_observed, flood_reached};
+pub use grid::contract_grid;
+pub use ida_star::ida_star;
+pub use lca::LcaTree;
+pub use mst::{kruskal, prim, SpanningTree};
+pub use observe::{Event, Observer};
+pub use parallel::{available_threads, parallel_astar, parallel_bfs};
+pub use scc::tarjan_scc;
+pub use topo::{topological_sort, Cycle};
@@ -0,0 +1,145 @@
+#![allow(dead_code)]
+
+use super::astar::Open;
+use super::{Cost, Node};
+use crate::{HashMap, HashSet};
+use std::collections::BinaryHeap;
+
+/// The edges of a minimum spanning tree, along with their total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningTree<N, C> {
+    pub edges: Vec<(N, N, C)>,
+    pub cost: C,
+}
+
+impl<N, C: Cost> SpanningTree<N, C> {
+    fn new() -> Self {
+        SpanningTree {
+            edges: Vec::new(),
+            cost: C::zero(),
+        }
+    }
+
+    fn push(&mut self, from: N, to: N, cost: C) {
+        self.cost = self.cost.clone() + cost.clone();
+        self.edges.push((from, to, cost));
+    }
+}
+
+/// Finds the root of the set containing `idx`, halving the path on the way.
+fn find(parents: &mut [usize], mut idx: usize) -> usize {
+    while parents[idx] != idx {
+        parents[idx] = parents[parents[idx]];
+        idx = parents[idx];
+    }
+    idx
+}
+
+/// Kruskal's algorithm over the undirected graph of `nodes`, where an edge
+/// may be given from either or both of its ends. If the graph isn't connected,
+/// the result is a spanning forest. Edges to unlisted nodes are ignored.
+pub fn kruskal<N, C, FN, NI>(nodes: &[N], mut next: FN) -> SpanningTree<N, C>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = (N, C)>,
+{
+    let indices: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let mut edges = Vec::new();
+    for (from, node) in nodes.iter().enumerate() {
+        for (successor, cost) in next(node) {
+            if let Some(&to) = indices.get(&successor) {
+                edges.push((cost, from, to));
+            }
+        }
+    }
+    edges.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut parents: Vec<usize> = (0..nodes.len()).collect();
+    let mut tree = SpanningTree::new();
+    for (cost, from, to) in edges {
+        let (a, b) = (find(&mut parents, from), find(&mut parents, to));
+        if a == b {
+            continue;
+        }
+        parents[a] = b;
+        tree.push(nodes[from].clone(), nodes[to].clone(), cost);
+        if tree.edges.len() + 1 == nodes.len() {
+            break;
+        }
+    }
+    tree
+}
+
+/// Prim's algorithm, growing a tree from `start` until it spans every node
+/// reachable from it. The edges must be undirected.
+pub fn prim<N, C, FN, NI>(start: N, mut next: FN) -> SpanningTree<N, C>
+where
+    N: Node,
+    C: Cost,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = (N, C)>,
+{
+    let mut in_tree = HashSet::new();
+    let mut open = BinaryHeap::new();
+    let mut counter = 0;
+    let mut tree = SpanningTree::new();
+
+    in_tree.insert(start.clone());
+    let mut newest = start;
+    loop {
+        for (successor, cost) in next(&newest) {
+            if in_tree.contains(&successor) {
+                continue;
+            }
+            counter += 1;
+            open.push(Open {
+                cost,
+                node: (newest.clone(), successor),
+                counter,
+            });
+        }
+
+        let edge = loop {
+            match open.pop() {
+                Some(edge) if in_tree.contains(&edge.node.1) => continue,
+                edge => break edge,
+            }
+        };
+        let Open {
+            cost,
+            node: (from, to),
+            ..
+        } = match edge {
+            Some(edge) => edge,
+            None => break,
+        };
+        in_tree.insert(to.clone());
+        tree.push(from, to.clone(), cost);
+        newest = to;
+    }
+    tree
+}
+
+#[test]
+fn spanning_trees() {
+    // A square with a diagonal, with the costs 1, 2, 3 and 4 around it and 2
+    // along the diagonal from 0 to 2
+    let edges = |&node: &u32| match node {
+        0 => vec![(1, 1), (3, 4), (2, 2)],
+        1 => vec![(0, 1), (2, 2)],
+        2 => vec![(1, 2), (3, 3), (0, 2)],
+        3 => vec![(2, 3), (0, 4)],
+        _ => vec![],
+    };
+    let by_kruskal = kruskal(&[0, 1, 2, 3], edges);
+    let by_prim = prim(0, edges);
+    assert_eq!((by_kruskal.cost, by_prim.cost), (6, 6));
+    assert_eq!((by_kruskal.edges.len(), by_prim.edges.len()), (3, 3));
+    assert_eq!(by_prim.edges[0], (0, 1, 1));
+
+    // Without 3, the edges only span the other nodes
+    let forest = kruskal(&[0, 1, 2, 5], edges);
+    assert_eq!((forest.cost, forest.edges.len()), (3, 2));
+}
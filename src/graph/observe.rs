@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+/// Something that happened during a search.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event<'a, N> {
+    /// The node was queued to be expanded, after it was reached from `from`.
+    /// `AStar` can open a node more than once, when it finds a cheaper route
+    /// to it before it's expanded. The other searches open every node once.
+    Opened { node: &'a N, from: Option<&'a N> },
+    /// The node was expanded, and won't be opened again.
+    Closed(&'a N),
+    /// The search reached its goal, the path starts at the initial node.
+    PathFound(&'a [N]),
+}
+
+impl<N> Clone for Event<'_, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<N> Copy for Event<'_, N> {}
+
+/// Receives the events of a search, see `AStar::solve_observed`,
+/// `bfs_observed` and `flood_observed`.
+pub trait Observer<N> {
+    fn observe(&mut self, event: Event<'_, N>);
+
+    /// Whether the observer looks at the events at all. Searches skip the
+    /// work that's only needed to report an event when it doesn't.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// The observer of the searches that aren't observed, which ignores every
+/// event.
+impl<N> Observer<N> for () {
+    #[inline(always)]
+    fn observe(&mut self, _: Event<'_, N>) {}
+
+    #[inline(always)]
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+impl<N, F: FnMut(Event<'_, N>)> Observer<N> for F {
+    fn observe(&mut self, event: Event<'_, N>) {
+        self(event)
+    }
+}
@@ -0,0 +1,261 @@
+#![allow(dead_code)]
+
+use super::astar::Open;
+use super::{Cost, Node};
+use crate::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::BinaryHeap;
+
+/// Batches smaller than this are expanded on the calling thread, since
+/// spawning threads would cost more than it saves.
+const MIN_PARALLEL_BATCH: usize = 32;
+
+/// The number of threads the machine can run in parallel.
+pub fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Applies `f` to every item, splitting the items into a chunk per thread.
+/// The results are in the same order as the items.
+fn expand<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if threads <= 1 || items.len() < MIN_PARALLEL_BATCH {
+        return items.iter().map(f).collect();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let f = &f;
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect()
+    })
+}
+
+/// A breadth first search that expands every layer across `threads` threads.
+/// Returns the path from `init` to the first node for which `is_done` holds.
+///
+/// The successors are merged back in the order of the layer, so the search
+/// visits the nodes in the same order and returns the same path for any
+/// number of threads.
+pub fn parallel_bfs<N, FN, FD, NI>(init: N, next: FN, is_done: FD, threads: usize) -> Option<Vec<N>>
+where
+    N: Node + Send + Sync,
+    FN: Fn(&N) -> NI + Sync,
+    FD: Fn(&N) -> bool + Sync,
+    NI: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    parents.insert(init.clone(), None);
+    let mut layer = vec![init];
+
+    while !layer.is_empty() {
+        let expanded = expand(&layer, threads, |node| {
+            if is_done(node) {
+                None
+            } else {
+                Some(next(node).into_iter().collect::<Vec<_>>())
+            }
+        });
+
+        let mut next_layer = Vec::new();
+        for (node, successors) in layer.iter().zip(expanded) {
+            let successors = match successors {
+                Some(successors) => successors,
+                None => {
+                    let mut path = vec![node.clone()];
+                    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+                        path.push(parent.clone());
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+            };
+            for successor in successors {
+                if let Entry::Vacant(entry) = parents.entry(successor.clone()) {
+                    entry.insert(Some(node.clone()));
+                    next_layer.push(successor);
+                }
+            }
+        }
+        layer = next_layer;
+    }
+
+    None
+}
+
+struct Meta<N, C> {
+    is_closed: bool,
+    path: C,
+    parent: Option<N>,
+}
+
+/// An A* search that expands all open nodes with the lowest estimate at once,
+/// across `threads` threads. The result is in the same form as `AStar::solve`.
+///
+/// The successors are merged back in the order the nodes were taken from the
+/// open set, so the search returns the same path for any number of threads.
+/// With a consistent heuristic its cost is the same as that of `AStar::solve`.
+pub fn parallel_astar<N, C, FN, FH, FD, NI>(
+    init: N,
+    next: FN,
+    heuristic: FH,
+    is_done: FD,
+    threads: usize,
+) -> Option<Vec<(N, C)>>
+where
+    N: Node + Send + Sync,
+    C: Cost + Send + Sync,
+    FN: Fn(&N) -> NI + Sync,
+    FH: Fn(&N) -> C + Sync,
+    FD: Fn(&N) -> bool + Sync,
+    NI: IntoIterator<Item = (N, C)>,
+{
+    let mut meta = HashMap::new();
+    let mut open = BinaryHeap::new();
+    // Used to get FIFO behaviour from the open set
+    let mut counter = 0;
+    meta.insert(
+        init.clone(),
+        Meta {
+            is_closed: false,
+            path: C::zero(),
+            parent: None,
+        },
+    );
+    open.push(Open {
+        cost: heuristic(&init),
+        node: init,
+        counter,
+    });
+
+    let mut batch = Vec::new();
+    while let Some(first) = open.pop() {
+        batch.clear();
+        let mut take = |node: N| {
+            let node_meta: &mut Meta<N, C> = meta.get_mut(&node).unwrap();
+            if !node_meta.is_closed {
+                node_meta.is_closed = true;
+                batch.push(node);
+            }
+        };
+        let estimate = first.cost;
+        take(first.node);
+        while open.peek().is_some_and(|open| open.cost == estimate) {
+            take(open.pop().unwrap().node);
+        }
+
+        let expanded = expand(&batch, threads, |node| {
+            if is_done(node) {
+                None
+            } else {
+                Some(
+                    next(node)
+                        .into_iter()
+                        .map(|(successor, edge_cost)| {
+                            let successor_heuristic = heuristic(&successor);
+                            (successor, edge_cost, successor_heuristic)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            }
+        });
+
+        for (node, successors) in batch.iter().zip(expanded) {
+            let successors = match successors {
+                Some(successors) => successors,
+                None => {
+                    let mut path = Vec::new();
+                    let mut current = Some(node);
+                    while let Some(n) = current {
+                        let n_meta = &meta[n];
+                        path.push((n.clone(), n_meta.path.clone()));
+                        current = n_meta.parent.as_ref();
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+            };
+            let path_cost = meta[node].path.clone();
+            for (successor, edge_cost, successor_heuristic) in successors {
+                let successor_cost = path_cost.clone() + edge_cost;
+                match meta.entry(successor.clone()) {
+                    Entry::Occupied(mut entry) => {
+                        let entry = entry.get_mut();
+                        if entry.is_closed || entry.path <= successor_cost {
+                            continue;
+                        }
+                        entry.path = successor_cost.clone();
+                        entry.parent = Some(node.clone());
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(Meta {
+                            is_closed: false,
+                            path: successor_cost.clone(),
+                            parent: Some(node.clone()),
+                        });
+                    }
+                }
+                counter += 1;
+                open.push(Open {
+                    cost: successor_cost + successor_heuristic,
+                    node: successor,
+                    counter,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[test]
+fn same_for_any_thread_count() {
+    use super::{astar_once, bfs_path};
+    // A 48x48 grid with scattered walls, big enough for the layers to be
+    // expanded in parallel
+    let is_open =
+        |x: i32, y: i32| (0..48).contains(&x) && (0..48).contains(&y) && (x * 7 + y * 13) % 11 != 0;
+    let next = |&(x, y): &(i32, i32)| {
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .iter()
+            .cloned()
+            .filter(|&(x, y)| is_open(x, y))
+            .map(|pos| (pos, 1))
+            .collect::<Vec<_>>()
+    };
+    let heuristic = |&(x, y): &(i32, i32)| (47 - x) + (47 - y);
+    let is_done = |&pos: &(i32, i32)| pos == (47, 47);
+    assert!(is_open(1, 1) && is_done(&(47, 47)) && is_open(47, 47));
+
+    let expected = astar_once((1, 1), next, heuristic, is_done).unwrap();
+    let single = parallel_astar((1, 1), next, heuristic, is_done, 1).unwrap();
+    assert_eq!(single.last().unwrap().1, expected.last().unwrap().1);
+    for threads in 2..5 {
+        assert_eq!(
+            parallel_astar((1, 1), next, heuristic, is_done, threads),
+            Some(single.clone())
+        );
+    }
+
+    let unweighted = |pos: &(i32, i32)| next(pos).into_iter().map(|(pos, _)| pos);
+    let path = bfs_path((1, 1), unweighted, is_done).unwrap();
+    for threads in 1..5 {
+        assert_eq!(
+            parallel_bfs((1, 1), unweighted, is_done, threads),
+            Some(path.clone())
+        );
+    }
+}
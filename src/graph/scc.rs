@@ -0,0 +1,107 @@
+#![allow(dead_code)]
+
+use super::Node;
+use crate::HashMap;
+
+struct Visit {
+    index: usize,
+    low_link: usize,
+    on_stack: bool,
+}
+
+/// Finds the strongly connected components of the graph reachable from
+/// `starts` using Tarjan's algorithm. A component is only returned after every
+/// component reachable from it, so the result is in reverse topological order.
+pub fn tarjan_scc<N, I, FN, NI>(starts: I, mut next: FN) -> Vec<Vec<N>>
+where
+    N: Node,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+{
+    let mut visits: HashMap<N, Visit> = HashMap::new();
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut calls: Vec<(N, NI::IntoIter)> = Vec::new();
+
+    let mut visit = |node: N,
+                     visits: &mut HashMap<N, Visit>,
+                     stack: &mut Vec<N>,
+                     calls: &mut Vec<(N, NI::IntoIter)>| {
+        let index = visits.len();
+        visits.insert(
+            node.clone(),
+            Visit {
+                index,
+                low_link: index,
+                on_stack: true,
+            },
+        );
+        stack.push(node.clone());
+        let successors = next(&node).into_iter();
+        calls.push((node, successors));
+    };
+
+    for start in starts {
+        if visits.contains_key(&start) {
+            continue;
+        }
+        visit(start, &mut visits, &mut stack, &mut calls);
+
+        while let Some((node, successors)) = calls.last_mut() {
+            if let Some(successor) = successors.next() {
+                match visits.get(&successor) {
+                    None => visit(successor, &mut visits, &mut stack, &mut calls),
+                    Some(&Visit {
+                        index,
+                        on_stack: true,
+                        ..
+                    }) => {
+                        let visit = visits.get_mut(node).unwrap();
+                        visit.low_link = visit.low_link.min(index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            let (node, _) = calls.pop().unwrap();
+            let (index, low_link) = {
+                let visit = &visits[&node];
+                (visit.index, visit.low_link)
+            };
+            if let Some((parent, _)) = calls.last() {
+                let parent = visits.get_mut(parent).unwrap();
+                parent.low_link = parent.low_link.min(low_link);
+            }
+            if index == low_link {
+                let from = stack.iter().rposition(|n| *n == node).unwrap();
+                let component: Vec<N> = stack.drain(from..).collect();
+                for n in &component {
+                    visits.get_mut(n).unwrap().on_stack = false;
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+#[test]
+fn components() {
+    // 0 -> 1 -> 2 -> 0 form a cycle that leads to 3 <-> 4, and 5 stands alone
+    let edges = |&node: &u32| match node {
+        0 => vec![1],
+        1 => vec![2],
+        2 => vec![0, 3],
+        3 => vec![4],
+        4 => vec![3],
+        _ => vec![],
+    };
+    let mut components = tarjan_scc(0..6, edges);
+    for component in &mut components {
+        component.sort_unstable();
+    }
+    assert_eq!(components, [vec![3, 4], vec![0, 1, 2], vec![5]]);
+}
@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use super::Node;
+use crate::HashMap;
+use std::fmt;
+
+/// The nodes of a cycle that prevents sorting, in the order of their edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the graph has a cycle of {} nodes", self.0.len())
+    }
+}
+
+impl<N: fmt::Debug> std::error::Error for Cycle<N> {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    InProgress,
+    Done,
+}
+
+/// Sorts the nodes reachable from `starts`, so that every node comes before
+/// all of its successors.
+pub fn topological_sort<N, I, FN, NI>(starts: I, mut next: FN) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Node,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> NI,
+    NI: IntoIterator<Item = N>,
+{
+    let mut marks = HashMap::new();
+    let mut sorted = Vec::new();
+    let mut stack: Vec<(N, NI::IntoIter)> = Vec::new();
+
+    for start in starts {
+        if marks.contains_key(&start) {
+            continue;
+        }
+        marks.insert(start.clone(), Mark::InProgress);
+        let successors = next(&start).into_iter();
+        stack.push((start, successors));
+
+        while let Some((_, successors)) = stack.last_mut() {
+            match successors.next() {
+                Some(successor) => match marks.get(&successor) {
+                    None => {
+                        marks.insert(successor.clone(), Mark::InProgress);
+                        let successors = next(&successor).into_iter();
+                        stack.push((successor, successors));
+                    }
+                    Some(Mark::InProgress) => {
+                        let from = stack
+                            .iter()
+                            .position(|(node, _)| *node == successor)
+                            .unwrap();
+                        return Err(Cycle(stack.drain(from..).map(|(node, _)| node).collect()));
+                    }
+                    Some(Mark::Done) => {}
+                },
+                None => {
+                    let (node, _) = stack.pop().unwrap();
+                    marks.insert(node.clone(), Mark::Done);
+                    sorted.push(node);
+                }
+            }
+        }
+    }
+
+    sorted.reverse();
+    Ok(sorted)
+}
+
+#[test]
+fn sort_and_cycle() {
+    let edges = |&node: &u32| match node {
+        1 => vec![2, 3],
+        2 => vec![4],
+        3 => vec![4],
+        _ => vec![],
+    };
+    let sorted = topological_sort(vec![1], edges).unwrap();
+    let position = |node| sorted.iter().position(|&n| n == node).unwrap();
+    assert_eq!(sorted.len(), 4);
+    assert!(position(1) < position(2) && position(2) < position(4));
+    assert!(position(3) < position(4));
+
+    let cyclic = |&node: &u32| vec![(node + 1) % 3];
+    assert_eq!(topological_sort(vec![0], cyclic), Err(Cycle(vec![0, 1, 2])));
+}
@@ -0,0 +1,38 @@
+use crate::HashMap;
+use std::collections::hash_map::{Entry, OccupiedEntry};
+use std::hash::Hash;
+
+/// The nodes a search has reached, along with what it keeps track of for each
+/// of them, such as the distance or the parent.
+#[derive(Debug, Clone)]
+pub(super) struct Visited<N, M> {
+    nodes: HashMap<N, M>,
+}
+
+impl<N: Hash + Eq, M> Visited<N, M> {
+    pub(super) fn new() -> Self {
+        Visited {
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Marks the node as visited. Returns its entry if it wasn't visited before.
+    pub(super) fn insert(&mut self, node: N, meta: M) -> Option<OccupiedEntry<'_, N, M>> {
+        match self.nodes.entry(node) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => Some(entry.insert_entry(meta)),
+        }
+    }
+
+    pub(super) fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    pub(super) fn get(&self, node: &N) -> Option<&M> {
+        self.nodes.get(node)
+    }
+
+    pub(super) fn into_map(self) -> HashMap<N, M> {
+        self.nodes
+    }
+}
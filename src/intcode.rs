@@ -1,6 +1,6 @@
 //! Intcode interpreter spec
 //!
-//! ```
+//! ```text
 //! Registers:
 //!     IP          Instruction pointer, holds the address of the next instruction to be executed
 //!     RelBase     Relative base, used as an offset to an address for parameter mode 2.
@@ -84,18 +84,14 @@ pub struct Registers {
     pub pending_in: Option<Value>,
     pub pending_out: Option<Value>,
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum State {
+    #[default]
     Idle,
     Halted,
     Reading,
     Writing,
 }
-impl Default for State {
-    fn default() -> Self {
-        State::Idle
-    }
-}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Opcode {
@@ -159,14 +155,14 @@ pub trait MemoryIntoData<T>: Memory + Sized + Clone + Default {
     fn into_data(self) -> T;
 }
 
-pub fn fixed_memory<I>(initial: I) -> impl Memory + MemoryIntoData<Vec<Value>>
+pub fn fixed_memory<I>(initial: I) -> impl MemoryIntoData<Vec<Value>>
 where
     I: IntoIterator<Item = Value>,
 {
     Vec::from_iter(initial)
 }
 
-pub fn sparse_memory<I>(initial: I) -> impl Memory + MemoryIntoData<HashMap<Value, Value>>
+pub fn sparse_memory<I>(initial: I) -> impl MemoryIntoData<HashMap<Value, Value>>
 where
     I: IntoIterator<Item = Value>,
 {
@@ -570,7 +566,7 @@ pub mod ascii {
                     Ok(())
                 }
                 IoOperation::Write(value) => {
-                    if value >= 0 && value < 128 {
+                    if (0..128).contains(&value) {
                         exec_op(AsciiOp::WriteAscii(value as u8 as char))
                     } else {
                         exec_op(AsciiOp::Write(value))
@@ -588,7 +584,7 @@ pub mod ascii {
                     .read_line(&mut read_buff)
                     .map_err(|err| Error::Custom(format!("cannot read from stdin ({:?})", err)))?;
 
-                let new_len = read_buff.trim_end_matches(|c| c == '\r' || c == '\n').len();
+                let new_len = read_buff.trim_end_matches(['\r', '\n']).len();
                 read_buff.truncate(new_len);
                 if read_buff == "#exit" {
                     return Ok(());
@@ -0,0 +1,52 @@
+//! Solutions for the Advent of Code 2019, along with the utilities they share.
+
+pub mod angle;
+pub mod cycle;
+pub mod direction;
+pub mod error;
+pub mod graph;
+pub mod intcode;
+pub mod mat2;
+pub mod matrix;
+#[macro_use]
+pub mod module;
+pub mod ocr;
+pub mod parsers;
+pub mod sparse_grid;
+pub mod vec2;
+pub mod vec3;
+
+pub use module::{find_day, Day};
+
+#[allow(dead_code)]
+type Atom = string_cache::DefaultAtom;
+pub type HashMap<K, V> = ahash::AHashMap<K, V>;
+pub type HashSet<T> = ahash::AHashSet<T>;
+
+generate_days!(
+    day01
+    day02
+    day03
+    day04
+    day05
+    day06
+    day07
+    day08
+    day09
+    day10
+    day11
+    day12
+    day13
+    day14
+    day15
+    day16
+    day17
+    day18
+    day19
+    day20
+    day21
+    day22
+    day23
+    day24
+    day25
+);
@@ -1,61 +1,50 @@
-#![feature(
-    auto_traits,
-    drain_filter,
-    entry_insert,
-    hash_set_entry,
-    is_sorted,
-    iter_intersperse,
-    negative_impls,
-    stmt_expr_attributes,
-    test,
-    trait_alias,
-    trusted_len
-)]
+use advent_of_code_2019::{module::*, Day, DAYS};
+use colored::Colorize;
+use std::path::Path;
 
-extern crate test;
+fn run_day(day: &Day, path: &str) {
+    execute_module_at(day.name, path, |input, mut closure| {
+        (day.run)(input, &mut |_| true, &mut |msg| {
+            execute_module_callback(&mut closure, msg)
+        })
+    });
+}
 
-pub(crate) mod direction;
-pub(crate) mod error;
-pub(crate) mod graph;
-pub(crate) mod intcode;
-pub(crate) mod mat2;
-#[macro_use]
-pub(crate) mod module;
-pub(crate) mod parsers;
-pub(crate) mod vec2;
-pub(crate) mod vec3;
+fn main() {
+    println!(
+        "{} {} {} {}",
+        "Advent".bright_red().bold(),
+        "of".bright_white(),
+        "Code".bright_green().bold(),
+        "2019".bright_blue()
+    );
 
-#[allow(dead_code)]
-type Atom = string_cache::DefaultAtom;
-#[allow(dead_code)]
-type HashMap<K, V> = ahash::AHashMap<K, V>;
-#[allow(dead_code)]
-type HashSet<T> = ahash::AHashSet<T>;
+    let exclusive_module = get_exclusive_module();
+    let exclusive_module = exclusive_module.as_deref();
+    let days = DAYS
+        .iter()
+        .filter(|day| exclusive_module.is_none() || exclusive_module == Some(day.name));
 
-generate_main!(
-    day01
-    day02
-    day03
-    day04
-    day05
-    day06
-    day07
-    day08
-    day09
-    day10
-    day11
-    day12
-    day13
-    day14
-    day15
-    day16
-    day17
-    day18
-    day19
-    day20
-    day21
-    day22
-    day23
-    day24
-    day25
-);
+    for day in days.clone() {
+        run_day(day, &day.input_path());
+    }
+
+    // Run against the inputs of every other account as well, to make sure the
+    // solutions don't rely on anything specific to one input.
+    if !std::env::args().any(|arg| arg == "--all-profiles") {
+        return;
+    }
+    for profile in profiles() {
+        println!(
+            "{} {}",
+            "profile".bright_white(),
+            profile.bright_blue().bold()
+        );
+        for day in days.clone() {
+            let path = profile_input_path(&profile, day.name);
+            if Path::new(&path).exists() {
+                run_day(day, &path);
+            }
+        }
+    }
+}
@@ -1,8 +1,19 @@
 // Matrix 2D backed by a vector
 #![allow(dead_code)]
-use crate::vec2::Vec2us;
+use crate::direction::{Direction, Direction8, GridDirection, MoveInDirection, StepInDirection};
+use crate::error::AoCError;
+use crate::module::Result;
+use crate::vec2::{Vec2i64, Vec2us};
 use std::ops::{Index, IndexMut};
 
+/// A matrix with a fixed size, indexed by `Vec2us` positions where `x` is the
+/// column and `y` the row.
+///
+/// The data is stored column-major, so `data[x * height + y]` is the item at
+/// `(x, y)`, `mat[x]` is the column `x` as a slice and `iter` goes down every
+/// column before moving to the next one. Most grids here are walked around in
+/// every direction, so neither layout is faster in general, but code that
+/// depends on the order should use `rows` or `columns` rather than `data`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Mat2<T: Clone> {
     pub data: Vec<T>,
@@ -23,23 +34,451 @@ impl<T: Clone> Mat2<T> {
 
     #[rustfmt::skip] #[inline(always)] pub fn iter(&self) -> Mat2Iter<'_, T> { self.into_iter() }
     #[rustfmt::skip] #[inline(always)] pub fn iter_mut(&mut self) -> Mat2IterMut<'_, T> { self.into_iter() }
+
+    pub fn contains(&self, pos: Vec2us) -> bool {
+        pos.x < self.size.x && pos.y < self.size.y
+    }
+
+    pub fn get(&self, pos: Vec2us) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.data[pos.x * self.size.y + pos.y])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2us) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.data[pos.x * self.size.y + pos.y])
+        } else {
+            None
+        }
+    }
+
+    /// The position a step away in `direction`, if it's inside the matrix.
+    pub fn step(&self, pos: Vec2us, direction: Direction) -> Option<Vec2us> {
+        pos.step_in_direction_checked(direction)
+            .filter(|&pos| self.contains(pos))
+    }
+
+    /// The position a step away in `direction`, where stepping off an edge
+    /// enters at the opposite edge.
+    pub fn step_wrapping(&self, pos: Vec2us, direction: Direction) -> Vec2us {
+        let pos = Vec2i64::new(pos.x as i64, pos.y as i64);
+        self.wrap(pos.step_in_direction(direction))
+    }
+
+    /// Wraps a position that may lie outside the matrix around its edges, as
+    /// if the matrix was tiled infinitely in every direction.
+    pub fn wrap(&self, pos: Vec2i64) -> Vec2us {
+        assert!(!self.data.is_empty(), "cannot wrap in an empty matrix");
+        Vec2us::new(
+            pos.x.rem_euclid(self.size.x as i64) as usize,
+            pos.y.rem_euclid(self.size.y as i64) as usize,
+        )
+    }
+
+    pub fn get_wrapping(&self, pos: Vec2i64) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    /// The orthogonal neighbors of `pos` inside the matrix, along with the
+    /// direction they're in.
+    pub fn neighbors4(&self, pos: Vec2us) -> impl Iterator<Item = (Direction, Vec2us, &T)> + '_ {
+        Direction::each().filter_map(move |dir| {
+            let next = self.step(pos, dir)?;
+            Some((dir, next, &self[next]))
+        })
+    }
+
+    /// Like `neighbors4`, but the neighbors of cells at an edge are at the
+    /// opposite edge.
+    pub fn neighbors4_wrapping(
+        &self,
+        pos: Vec2us,
+    ) -> impl Iterator<Item = (Direction, Vec2us, &T)> + '_ {
+        Direction::each().map(move |dir| {
+            let next = self.step_wrapping(pos, dir);
+            (dir, next, &self[next])
+        })
+    }
+
+    /// The orthogonal and diagonal neighbors of `pos` inside the matrix,
+    /// along with the direction they're in, clockwise from the one above it.
+    pub fn neighbors8(&self, pos: Vec2us) -> impl Iterator<Item = (Direction8, Vec2us, &T)> + '_ {
+        Direction8::all().filter_map(move |dir| {
+            let next = pos.step_checked(dir)?;
+            self.get(next).map(|item| (dir, next, item))
+        })
+    }
+
+    /// Like `neighbors8`, but the neighbors of cells at an edge are at the
+    /// opposite edge.
+    pub fn neighbors8_wrapping(
+        &self,
+        pos: Vec2us,
+    ) -> impl Iterator<Item = (Direction8, Vec2us, &T)> + '_ {
+        let signed = Vec2i64::new(pos.x as i64, pos.y as i64);
+        Direction8::all().map(move |dir| {
+            let (dx, dy) = dir.offset();
+            let next = self.wrap(signed + Vec2i64::new(dx as i64, dy as i64));
+            (dir, next, &self[next])
+        })
+    }
+
+    /// Which cells can be reached from `start` through orthogonal steps, only
+    /// entering the cells for which `can_enter` holds. `start` itself is
+    /// always reached.
+    pub fn flood_fill<F>(&self, start: Vec2us, mut can_enter: F) -> Mat2<bool>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut reached = Mat2::new(false, self.size);
+        reached[start] = true;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for (_, next, item) in self.neighbors4(pos) {
+                if !reached[next] && can_enter(item) {
+                    reached[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Parses a grid with a line per row, converting every character with
+    /// `f`. All lines need to be as long as the first one. Errors are wrapped
+    /// in `AoCError::AtPosition`, with 1-based line and column numbers.
+    pub fn from_text<F>(s: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut rows = Vec::new();
+        let mut width = None;
+        for (y, line) in s.lines().enumerate() {
+            let mut length = 0;
+            for (x, c) in line.chars().enumerate() {
+                if width.is_some_and(|width| x >= width) {
+                    return Err(at(y, x, AoCError::IncorrectInput("line is too long")));
+                }
+                rows.push(f(c).map_err(|err| at(y, x, err))?);
+                length += 1;
+            }
+            match width {
+                Some(width) if length < width => {
+                    return Err(at(y, length, AoCError::IncorrectInput("line is too short")))
+                }
+                Some(_) => {}
+                None => width = Some(length),
+            }
+        }
+        let width = match width {
+            Some(width) if width > 0 => width,
+            _ => return Err(AoCError::IncorrectInput("empty grid")),
+        };
+        let height = rows.len() / width;
+
+        // The rows were parsed in row-major order, but the data is stored
+        // column-major
+        let mut data = Vec::with_capacity(rows.len());
+        for x in 0..width {
+            data.extend((0..height).map(|y| rows[y * width + x].clone()));
+        }
+        Ok(Mat2 {
+            data,
+            size: Vec2us::new(width, height),
+        })
+    }
+
+    /// Parses a grid like `from_text`, but lines may have different lengths.
+    /// The grid is as wide as the longest line, and the cells past the end of
+    /// shorter lines are set to `fill`.
+    pub fn from_text_padded<F>(s: &str, fill: T, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let width = s
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        if width == 0 {
+            return Err(AoCError::IncorrectInput("empty grid"));
+        }
+        let mut grid = Mat2::new(fill, Vec2us::new(width, s.lines().count()));
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid[Vec2us::new(x, y)] = f(c).map_err(|err| at(y, x, err))?;
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Renders the grid with a line per row, which each end with a newline,
+    /// in the form `from_text` parses.
+    pub fn render<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut s = String::with_capacity((self.width() + 1) * self.height());
+        for row in self.rows() {
+            s.extend(row.map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// The items of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> &[T] {
+        &self[x]
+    }
+
+    pub fn get_column(&self, x: usize) -> Option<&[T]> {
+        if x < self.size.x {
+            let base = x * self.size.y;
+            Some(&self.data[base..base + self.size.y])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_column_mut(&mut self, x: usize) -> Option<&mut [T]> {
+        if x < self.size.x {
+            let base = x * self.size.y;
+            Some(&mut self.data[base..base + self.size.y])
+        } else {
+            None
+        }
+    }
+
+    /// The items of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> impl ExactSizeIterator<Item = &T> + Clone {
+        assert!(y < self.size.y);
+        (0..self.size.x).map(move |x| &self.data[x * self.size.y + y])
+    }
+
+    /// The columns from left to right.
+    pub fn columns(&self) -> impl ExactSizeIterator<Item = &[T]> + Clone {
+        (0..self.size.x).map(move |x| self.column(x))
+    }
+
+    /// The rows from top to bottom.
+    pub fn rows(
+        &self,
+    ) -> impl ExactSizeIterator<Item = impl ExactSizeIterator<Item = &T> + Clone> + Clone {
+        (0..self.size.y).map(move |y| self.row(y))
+    }
+
+    /// Borrows the rectangle of `size` items starting at `offset`.
+    pub fn view(&self, offset: Vec2us, size: Vec2us) -> Mat2View<'_, T> {
+        Mat2View::new(self, offset, size)
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Mat2<U>
+    where
+        U: Clone,
+        F: FnMut(&T) -> U,
+    {
+        Mat2 {
+            data: self.data.iter().map(&mut f).collect(),
+            size: self.size,
+        }
+    }
+
+    /// Combines the items at the same positions of two matrices of the same
+    /// size.
+    pub fn zip_with<U, V, F>(&self, other: &Mat2<U>, mut f: F) -> Mat2<V>
+    where
+        U: Clone,
+        V: Clone,
+        F: FnMut(&T, &U) -> V,
+    {
+        assert_eq!(self.size, other.size, "matrices differ in size");
+        Mat2 {
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            size: self.size,
+        }
+    }
+
+    /// Creates a matrix of `size` by calling `f` for every position.
+    pub fn from_fn<F>(size: Vec2us, mut f: F) -> Self
+    where
+        F: FnMut(Vec2us) -> T,
+    {
+        let mut data = Vec::with_capacity(size.x * size.y);
+        for x in 0..size.x {
+            data.extend((0..size.y).map(|y| f(Vec2us::new(x, y))));
+        }
+        Mat2 { data, size }
+    }
+
+    /// Mirrors the matrix along its diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        Mat2::from_fn(Vec2us::new(self.size.y, self.size.x), |pos| {
+            self[Vec2us::new(pos.y, pos.x)].clone()
+        })
+    }
+
+    /// Rotates the matrix a quarter turn clockwise, assuming `y` points down.
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.size.y;
+        Mat2::from_fn(Vec2us::new(self.size.y, self.size.x), |pos| {
+            self[Vec2us::new(pos.y, height - 1 - pos.x)].clone()
+        })
+    }
+
+    /// Rotates the matrix a quarter turn counterclockwise, assuming `y`
+    /// points down.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let width = self.size.x;
+        Mat2::from_fn(Vec2us::new(self.size.y, self.size.x), |pos| {
+            self[Vec2us::new(width - 1 - pos.y, pos.x)].clone()
+        })
+    }
+
+    /// Mirrors the matrix left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.size.x;
+        Mat2::from_fn(self.size, |pos| {
+            self[Vec2us::new(width - 1 - pos.x, pos.y)].clone()
+        })
+    }
+
+    /// Mirrors the matrix top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.size.y;
+        Mat2::from_fn(self.size, |pos| {
+            self[Vec2us::new(pos.x, height - 1 - pos.y)].clone()
+        })
+    }
+}
+
+/// A borrowed rectangle of a `Mat2`, with its own positions starting at the
+/// top left of the rectangle.
+#[derive(Debug)]
+pub struct Mat2View<'a, T: Clone> {
+    mat: &'a Mat2<T>,
+    offset: Vec2us,
+    size: Vec2us,
+}
+
+impl<T: Clone> Clone for Mat2View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T: Clone> Copy for Mat2View<'_, T> {}
+
+impl<'a, T: Clone> Mat2View<'a, T> {
+    fn new(mat: &'a Mat2<T>, offset: Vec2us, size: Vec2us) -> Self {
+        assert!(
+            offset.x + size.x <= mat.size.x && offset.y + size.y <= mat.size.y,
+            "view out of bounds"
+        );
+        Mat2View { mat, offset, size }
+    }
+
+    #[rustfmt::skip] #[inline(always)] pub fn size(&self) -> Vec2us { self.size }
+    #[rustfmt::skip] #[inline(always)] pub fn width(&self) -> usize { self.size.x }
+    #[rustfmt::skip] #[inline(always)] pub fn height(&self) -> usize { self.size.y }
+    #[rustfmt::skip] #[inline(always)] pub fn offset(&self) -> Vec2us { self.offset }
+
+    /// The items of column `x` of the view, from top to bottom.
+    pub fn column(&self, x: usize) -> &'a [T] {
+        assert!(x < self.size.x);
+        &self.mat[self.offset.x + x][self.offset.y..self.offset.y + self.size.y]
+    }
+
+    /// The items of row `y` of the view, from left to right.
+    pub fn row(&self, y: usize) -> impl ExactSizeIterator<Item = &'a T> + Clone {
+        assert!(y < self.size.y);
+        let (mat, offset) = (self.mat, self.offset);
+        (0..self.size.x).map(move |x| &mat[Vec2us::new(offset.x + x, offset.y + y)])
+    }
+
+    pub fn columns(&self) -> impl ExactSizeIterator<Item = &'a [T]> + Clone {
+        let view = *self;
+        (0..self.size.x).map(move |x| view.column(x))
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl ExactSizeIterator<Item = impl ExactSizeIterator<Item = &'a T> + Clone> + Clone {
+        let view = *self;
+        (0..self.size.y).map(move |y| view.row(y))
+    }
+
+    /// Iterates over the view in the same order as `Mat2::iter`, with the
+    /// positions relative to the view.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2us, &'a T)> + Clone {
+        let view = *self;
+        (0..self.size.x).flat_map(move |x| {
+            view.column(x)
+                .iter()
+                .enumerate()
+                .map(move |(y, item)| (Vec2us::new(x, y), item))
+        })
+    }
+
+    /// Borrows a rectangle of this view, with `offset` relative to the view.
+    pub fn view(&self, offset: Vec2us, size: Vec2us) -> Mat2View<'a, T> {
+        assert!(
+            offset.x + size.x <= self.size.x && offset.y + size.y <= self.size.y,
+            "view out of bounds"
+        );
+        Mat2View::new(self.mat, self.offset + offset, size)
+    }
+
+    /// Copies the view into a matrix of its own.
+    pub fn to_mat2(&self) -> Mat2<T> {
+        let view = *self;
+        Mat2::from_fn(self.size, |pos| view[pos].clone())
+    }
+}
+
+impl<T: Clone> Index<Vec2us> for Mat2View<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Vec2us) -> &T {
+        assert!(index.x < self.size.x && index.y < self.size.y);
+        &self.mat[self.offset + index]
+    }
+}
+
+/// Indexing panics when out of bounds, see `get` and `get_column` for the
+/// fallible alternatives.
+fn at(line: usize, column: usize, err: AoCError) -> AoCError {
+    AoCError::AtPosition {
+        line: line + 1,
+        column: column + 1,
+        source: Box::new(err),
+    }
 }
 
 impl<T: Clone> Index<usize> for Mat2<T> {
     type Output = [T];
 
     fn index(&self, index: usize) -> &[T] {
-        assert!(index < self.size.x);
-        let base = index * self.size.y;
-        &self.data[base..base + self.size.y]
+        match self.get_column(index) {
+            Some(column) => column,
+            None => panic!("column {} out of bounds for size {}", index, self.size),
+        }
     }
 }
 
 impl<T: Clone> IndexMut<usize> for Mat2<T> {
     fn index_mut(&mut self, index: usize) -> &mut [T] {
-        assert!(index < self.size.x);
-        let base = index * self.size.y;
-        &mut self.data[base..base + self.size.y]
+        let size = self.size;
+        match self.get_column_mut(index) {
+            Some(column) => column,
+            None => panic!("column {} out of bounds for size {}", index, size),
+        }
     }
 }
 
@@ -47,15 +486,20 @@ impl<T: Clone> Index<Vec2us> for Mat2<T> {
     type Output = T;
 
     fn index(&self, index: Vec2us) -> &T {
-        assert!(index.x < self.size.x);
-        &self.data[index.x * self.size.y + index.y]
+        match self.get(index) {
+            Some(item) => item,
+            None => panic!("position {} out of bounds for size {}", index, self.size),
+        }
     }
 }
 
 impl<T: Clone> IndexMut<Vec2us> for Mat2<T> {
     fn index_mut(&mut self, index: Vec2us) -> &mut T {
-        assert!(index.x < self.size.x);
-        &mut self.data[index.x * self.size.y + index.y]
+        let size = self.size;
+        match self.get_mut(index) {
+            Some(item) => item,
+            None => panic!("position {} out of bounds for size {}", index, size),
+        }
     }
 }
 
@@ -138,3 +582,149 @@ implement_iterator!(
     into_iter_for[&'a mut Mat2<T>],
     into_iter_fn[iter_mut],
 );
+
+#[test]
+fn text() {
+    let parse = |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(AoCError::IncorrectInput("expected '#' or '.'")),
+    };
+    let grid = Mat2::from_text("#..\n.##\n", parse).unwrap();
+    assert_eq!(grid.size(), Vec2us::new(3, 2));
+    assert!(grid[Vec2us::new(0, 0)] && grid[Vec2us::new(2, 1)] && !grid[Vec2us::new(2, 0)]);
+    let text = grid.render(|&b| if b { '#' } else { '.' });
+    assert_eq!(text, "#..\n.##\n");
+    assert_eq!(Mat2::from_text(&text, parse).unwrap(), grid);
+
+    let position = |s| match Mat2::from_text(s, parse) {
+        Err(AoCError::AtPosition { line, column, .. }) => Some((line, column)),
+        _ => None,
+    };
+    assert_eq!(position("#..\n.x."), Some((2, 2)));
+    assert_eq!(position("#..\n.#"), Some((2, 3)));
+    assert_eq!(position("#..\n.#.#"), Some((2, 4)));
+    assert!(Mat2::from_text("", parse).is_err());
+}
+
+#[test]
+fn text_padded() {
+    let parse = |c| match c {
+        '#' => Ok(1),
+        '.' => Ok(0),
+        _ => Err(AoCError::IncorrectInput("expected '#' or '.'")),
+    };
+    let grid = Mat2::from_text_padded("#\n.##\n#.", 2, parse).unwrap();
+    assert_eq!(grid.size(), Vec2us::new(3, 3));
+    assert_eq!(grid.render(|&n| (b'0' + n) as char), "122\n011\n102\n");
+    assert!(matches!(
+        Mat2::from_text_padded("#\n.x", 2, parse),
+        Err(AoCError::AtPosition { line: 2, column: 2, .. })
+    ));
+    assert!(Mat2::from_text_padded("\n\n", 2, parse).is_err());
+}
+
+#[test]
+fn layout() {
+    // Column-major, so the second item is below the first one
+    let mat = Mat2::from_text("ab\ncd\nef\n", Ok).unwrap();
+    assert_eq!(mat.data, ['a', 'c', 'e', 'b', 'd', 'f']);
+    assert_eq!(mat[1], ['b', 'd', 'f']);
+    assert_eq!(mat.column(0), ['a', 'c', 'e']);
+    let rows: Vec<String> = mat.rows().map(|row| row.collect()).collect();
+    assert_eq!(rows, ["ab", "cd", "ef"]);
+    let columns: Vec<String> = mat
+        .columns()
+        .map(|column| column.iter().collect())
+        .collect();
+    assert_eq!(columns, ["ace", "bdf"]);
+    let order: String = mat.iter().map(|(_, &c)| c).collect();
+    assert_eq!(order, "acebdf");
+}
+
+#[test]
+fn transform() {
+    let mat = Mat2::from_text("abc\ndef\n", Ok).unwrap();
+    let render = |mat: &Mat2<char>| mat.render(|&c| c);
+    assert_eq!(render(&mat.transpose()), "ad\nbe\ncf\n");
+    assert_eq!(render(&mat.rotate_clockwise()), "da\neb\nfc\n");
+    assert_eq!(render(&mat.rotate_counterclockwise()), "cf\nbe\nad\n");
+    assert_eq!(render(&mat.flip_horizontal()), "cba\nfed\n");
+    assert_eq!(render(&mat.flip_vertical()), "def\nabc\n");
+    assert_eq!(mat.rotate_clockwise().rotate_counterclockwise(), mat);
+    assert_eq!(
+        mat.rotate_clockwise().rotate_clockwise(),
+        mat.flip_horizontal().flip_vertical()
+    );
+
+    let upper = mat.map(|c| c.to_ascii_uppercase());
+    assert_eq!(render(&upper), "ABC\nDEF\n");
+    let same = mat.zip_with(&upper, |a, b| a.to_ascii_uppercase() == *b);
+    assert!(same.data.iter().all(|&same| same));
+
+    let view = mat.view(Vec2us::new(1, 0), Vec2us::new(2, 2));
+    assert_eq!(view.to_mat2().render(|&c| c), "bc\nef\n");
+    assert_eq!(view.column(1), ['c', 'f']);
+    let rows: Vec<String> = view.rows().map(|row| row.collect()).collect();
+    assert_eq!(rows, ["bc", "ef"]);
+    let inner = view.view(Vec2us::new(1, 1), Vec2us::new(1, 1));
+    assert_eq!(inner[Vec2us::new(0, 0)], 'f');
+    let order: Vec<_> = view.iter().map(|(pos, &c)| (pos.x, pos.y, c)).collect();
+    assert_eq!(order, [(0, 0, 'b'), (0, 1, 'e'), (1, 0, 'c'), (1, 1, 'f')]);
+}
+
+#[test]
+fn neighbors() {
+    let mat = Mat2::from_text("abc\ndef\nghi\n", Ok).unwrap();
+    assert_eq!(mat.get(Vec2us::new(2, 1)), Some(&'f'));
+    assert_eq!(mat.get(Vec2us::new(1, 3)), None);
+    assert_eq!(mat.get(Vec2us::new(3, 0)), None);
+    assert_eq!(mat.get_column(3), None);
+    assert_eq!(*mat.get_wrapping(Vec2i64::new(-1, 4)), 'f');
+
+    let corner: Vec<_> = mat
+        .neighbors4(Vec2us::new(0, 0))
+        .map(|(dir, _, &c)| (dir, c))
+        .collect();
+    assert_eq!(corner, [(Direction::South, 'd'), (Direction::East, 'b')]);
+    let wrapped: String = mat
+        .neighbors4_wrapping(Vec2us::new(0, 0))
+        .map(|(_, _, &c)| c)
+        .collect();
+    assert_eq!(wrapped, "gdcb");
+    let around: String = mat
+        .neighbors8(Vec2us::new(1, 1))
+        .map(|(_, _, &c)| c)
+        .collect();
+    assert_eq!(around, "bcfihgda");
+    let corner: Vec<_> = mat
+        .neighbors8(Vec2us::new(0, 0))
+        .map(|(dir, _, &c)| (dir, c))
+        .collect();
+    assert_eq!(
+        corner,
+        [
+            (Direction8::East, 'b'),
+            (Direction8::SouthEast, 'e'),
+            (Direction8::South, 'd')
+        ]
+    );
+    let wrapped: String = mat
+        .neighbors8_wrapping(Vec2us::new(0, 0))
+        .map(|(_, _, &c)| c)
+        .collect();
+    assert_eq!(wrapped, "ghbedfci");
+
+    let maze = Mat2::from_text("..#.\n.##.\n...#\n", Ok).unwrap();
+    let reached = maze.flood_fill(Vec2us::new(0, 0), |&c| c == '.');
+    let reached = reached.render(|&reached| if reached { 'x' } else { ' ' });
+    assert_eq!(reached, "xx  \nx   \nxxx \n");
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn index_out_of_bounds() {
+    // The position maps to an item of the next column in `data`
+    let mat = Mat2::new(0, Vec2us::new(2, 2));
+    let _ = mat[Vec2us::new(0, 2)];
+}
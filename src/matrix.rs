@@ -0,0 +1,215 @@
+//! Small square matrices for transforming `Vec2` and `Vec3`, mostly used for
+//! rotating and mirroring positions on a grid.
+#![allow(dead_code)]
+
+use crate::vec2::Vec2;
+use crate::vec3::Vec3;
+use num::traits::{One, Signed, Zero};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A 2x2 matrix, stored row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix2<T> {
+    pub rows: [[T; 2]; 2],
+}
+
+/// A 3x3 matrix, stored row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix3<T> {
+    pub rows: [[T; 3]; 3],
+}
+
+impl<T: Copy> Matrix2<T> {
+    #[inline(always)]
+    pub const fn new(rows: [[T; 2]; 2]) -> Self {
+        Matrix2 { rows }
+    }
+
+    pub fn transpose(self) -> Self {
+        Matrix2::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.rows[j][i])
+        }))
+    }
+}
+
+impl<T: Copy + Zero + One> Matrix2<T> {
+    pub fn identity() -> Self {
+        Matrix2::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| if i == j { T::one() } else { T::zero() })
+        }))
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Matrix2<T> {
+    pub fn determinant(self) -> T {
+        let [[a, b], [c, d]] = self.rows;
+        a * d - b * c
+    }
+}
+
+impl<T: Copy + Zero + One + Neg<Output = T>> Matrix2<T> {
+    /// Rotates a quarter turn clockwise, with `y` pointing down like
+    /// `Direction` does.
+    pub fn rotate_clockwise() -> Self {
+        let (o, l) = (T::zero(), T::one());
+        Matrix2::new([[o, -l], [l, o]])
+    }
+
+    /// Mirrors left to right, negating `x`.
+    pub fn mirror_x() -> Self {
+        let (o, l) = (T::zero(), T::one());
+        Matrix2::new([[-l, o], [o, l]])
+    }
+}
+
+impl<T: Copy + Zero + One> Matrix3<T> {
+    pub fn identity() -> Self {
+        Matrix3::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| if i == j { T::one() } else { T::zero() })
+        }))
+    }
+}
+
+impl<T: Copy> Matrix3<T> {
+    #[inline(always)]
+    pub const fn new(rows: [[T; 3]; 3]) -> Self {
+        Matrix3 { rows }
+    }
+
+    pub fn transpose(self) -> Self {
+        Matrix3::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.rows[j][i])
+        }))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>> Matrix3<T> {
+    pub fn determinant(self) -> T {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.rows;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+}
+
+macro_rules! impl_mul {
+    ($matrix:ident, $vec:ident, $n:expr, [$($field:ident),+]) => {
+        impl<T: Copy + Zero + Mul<Output = T>> Mul for $matrix<T> {
+            type Output = Self;
+            fn mul(self, other: Self) -> Self {
+                $matrix::new(std::array::from_fn(|i| {
+                    std::array::from_fn(|j| {
+                        (0..$n).fold(T::zero(), |sum, k| sum + self.rows[i][k] * other.rows[k][j])
+                    })
+                }))
+            }
+        }
+
+        impl<T: Copy + Zero + Mul<Output = T>> Mul<$vec<T>> for $matrix<T> {
+            type Output = $vec<T>;
+            fn mul(self, vec: $vec<T>) -> $vec<T> {
+                let column = [$(vec.$field),+];
+                let [$($field),+] = std::array::from_fn(|i| {
+                    (0..$n).fold(T::zero(), |sum, k| sum + self.rows[i][k] * column[k])
+                });
+                $vec::new($($field),+)
+            }
+        }
+    };
+}
+
+impl_mul!(Matrix2, Vec2, 2, [x, y]);
+impl_mul!(Matrix3, Vec3, 3, [x, y, z]);
+
+/// The 8 ways to place a square on itself: the 4 rotations, clockwise from
+/// the identity, followed by the same rotations after mirroring.
+pub fn square_orientations<T>() -> [Matrix2<T>; 8]
+where
+    T: Copy + Zero + One + Neg<Output = T>,
+{
+    let mut orientations = [Matrix2::identity(); 8];
+    for i in 1..4 {
+        orientations[i] = Matrix2::rotate_clockwise() * orientations[i - 1];
+    }
+    for i in 4..8 {
+        orientations[i] = orientations[i - 4] * Matrix2::mirror_x();
+    }
+    orientations
+}
+
+/// The 24 rotations of a cube, which are the matrices that permute the axes
+/// and flip some of them, without mirroring. Starts with the identity.
+pub fn cube_rotations<T>() -> [Matrix3<T>; 24]
+where
+    T: Copy + Signed,
+{
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut rotations = [Matrix3::identity(); 24];
+    let mut count = 0;
+    for permutation in PERMUTATIONS.iter() {
+        for signs in 0..8 {
+            let matrix = Matrix3::new(std::array::from_fn(|i| {
+                std::array::from_fn(|j| match (j == permutation[i], (signs >> i) & 1) {
+                    (false, _) => T::zero(),
+                    (true, 0) => T::one(),
+                    (true, _) => -T::one(),
+                })
+            }));
+            // The other half mirrors
+            if matrix.determinant().is_positive() {
+                rotations[count] = matrix;
+                count += 1;
+            }
+        }
+    }
+    debug_assert_eq!(count, 24);
+    rotations
+}
+
+#[test]
+fn transforms() {
+    use crate::vec2::Vec2i;
+    use crate::vec3::Vec3i;
+    let pos = Vec2i::new(2, -1);
+    let rotate = Matrix2::rotate_clockwise();
+    assert_eq!(rotate * pos, pos.rotate_clockwise());
+    assert_eq!(rotate.transpose() * pos, pos.rotate_counterclockwise());
+    assert_eq!(rotate * rotate * rotate * rotate, Matrix2::identity());
+
+    let squares = square_orientations::<i32>();
+    assert_eq!(squares[0], Matrix2::identity());
+    let mut placed: Vec<_> = squares.iter().map(|&m| m * pos).collect();
+    placed.sort_unstable();
+    placed.dedup();
+    assert_eq!(placed.len(), 8);
+    assert!(squares[..4].iter().all(|m| m.determinant() == 1));
+    assert!(squares[4..].iter().all(|m| m.determinant() == -1));
+
+    let cubes = cube_rotations::<i32>();
+    assert_eq!(cubes[0], Matrix3::identity());
+    let pos = Vec3i::new(1, 2, 3);
+    let mut placed: Vec<_> = cubes.iter().map(|&m| m * pos).collect();
+    placed.sort_unstable();
+    placed.dedup();
+    assert_eq!(placed.len(), 24);
+    // Rotations are closed under composition
+    assert!(cubes.contains(&(cubes[5] * cubes[17])));
+    assert!(cubes
+        .iter()
+        .all(|&m| m * m.transpose() == Matrix3::identity()));
+
+    assert_eq!(pos.cross(Vec3i::new(4, 5, 6)), Vec3i::new(-3, 6, -3));
+    assert_eq!(Vec3i::new(0, -4, 6).normalized(), Vec3i::new(0, -2, 3));
+    assert_eq!(Vec2i::new(-6, 9).normalized(), Vec2i::new(-2, 3));
+    assert_eq!(Vec2i::new(0, 0).normalized(), Vec2i::new(0, 0));
+    assert_eq!(Vec2i::new(1, 0).cross(Vec2i::new(0, 1)), 1);
+    assert_eq!(pos.manhattan_distance(Vec3i::new(-1, 2, 0)), 5);
+    assert_eq!(pos.chebyshev_distance(Vec3i::new(-1, 2, 0)), 3);
+    assert_eq!(Vec2i::new(3, -4).manhattan_distance(Vec2i::new(0, 0)), 7);
+    assert_eq!(Vec2i::new(3, -4).chebyshev_distance(Vec2i::new(0, 0)), 4);
+}
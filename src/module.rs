@@ -1,62 +1,111 @@
 macro_rules! module_part {
-    ($input:expr, $result:expr, $part_name:ident, $parser:ident) => {
+    ($input:expr, $part_name:ident, $parser:ident) => {{
         use crate::module::ToModuleResult;
-        let parsed = $parser($input);
-        *$result = Some(
-            parsed
-                .to_module_result()
-                .and_then(|parsed| $part_name(parsed).to_module_result()),
-        );
-    };
-    ($input:expr, $result:expr, $part_name:ident) => {
+        $parser($input)
+            .to_module_result()
+            .and_then(|parsed| $part_name(parsed).to_module_result())
+            .map(|result| result.to_string())
+    }};
+    ($input:expr, $part_name:ident) => {{
         use crate::module::ToModuleResult;
-        *$result = Some($part_name($input).to_module_result());
+        $part_name($input)
+            .to_module_result()
+            .map(|result| result.to_string())
+    }};
+}
+
+macro_rules! module_time_budget {
+    () => {
+        crate::module::DEFAULT_TIME_BUDGET
+    };
+    ($budget:expr) => {
+        $budget
     };
 }
 
 #[macro_export]
 macro_rules! module {
-    ($($part_name:ident$(: $parser:ident)?),*) => {
+    ($($part_name:ident$(: $parser:ident)?),*$(; time_budget: $budget:expr)?) => {
         #[allow(unused_imports)]
-        use {crate::{parsers, error::AoCError, module::Result}, nom::IResult};
+        use {$crate::{parsers, error::AoCError, module::Result}, nom::IResult};
+        /// Maximum time each part may run before it is reported as timed out.
+        pub const TIME_BUDGET: std::time::Duration = module_time_budget!($($budget)?);
+
         #[allow(dead_code)]
-        pub(crate) fn module<Out>(input: &str, mut out: Out)
+        pub fn module<Out>(input: &str, out: Out)
         where
-            Out: FnMut(crate::module::Message<'static>) -> ()
+            Out: FnMut($crate::module::Message<'static>) -> ()
         {
-            $({
-                out(crate::module::Message::Start(stringify!($part_name)));
-                let mut result = None;
-                module_part!(input, &mut result, $part_name$(, $parser)?);
-                let result = result.unwrap();
-                use std::string::ToString;
-                out(crate::module::Message::Finish(stringify!($part_name), result.map(|result| result.to_string())));
+            module_filtered(input, |_| true, out)
+        }
+
+        /// Runs only the parts for which `filter` returns true, each on a
+        /// worker thread limited to `TIME_BUDGET`.
+        #[allow(dead_code)]
+        pub fn module_filtered<Filter, Out>(input: &str, mut filter: Filter, mut out: Out)
+        where
+            Filter: FnMut(&'static str) -> bool,
+            Out: FnMut($crate::module::Message<'static>) -> ()
+        {
+            $(if filter(stringify!($part_name)) {
+                out($crate::module::Message::Start(stringify!($part_name)));
+                let input = input.to_owned();
+                let result = $crate::module::run_part(TIME_BUDGET, move || {
+                    module_part!(&input, $part_name$(, $parser)?)
+                });
+                out($crate::module::Message::Finish(stringify!($part_name), result));
             })*
         }
+
+        /// Runs a single part on the current thread, without a time budget or
+        /// catching panics. Returns `None` if there's no part named `part`.
+        #[allow(dead_code)]
+        pub fn solve_part(part: &str, input: &str) -> Option<Result<String>> {
+            match part {
+                $(stringify!($part_name) => Some(module_part!(input, $part_name$(, $parser)?)),)*
+                _ => None,
+            }
+        }
     };
 }
 
 use crate::error::AoCError;
 use nom::IResult;
+use std::cell::{Cell, RefCell};
 use std::fmt::Debug;
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
+use std::time::Duration;
 
 pub type Result<T> = ::std::result::Result<T, crate::error::AoCError>;
 
-pub auto trait IsNotResult {}
-impl<T, E> !IsNotResult for std::result::Result<T, E> {}
+/// Converts the return value of a parser or part into a `Result`.
+///
+/// Plain answers are implemented per type by `impl_to_module_result!`, since
+/// a blanket impl for every non-`Result` type isn't expressible on stable.
 pub trait ToModuleResult: Sized {
     type Output;
     fn to_module_result(self) -> Self::Output;
 }
-impl<T> ToModuleResult for T
-where
-    T: IsNotResult,
-{
-    type Output = Result<Self>;
-    fn to_module_result(self) -> Self::Output {
-        Ok(self)
-    }
+
+macro_rules! impl_to_module_result {
+    ($($t:ty),*$(,)?) => {
+        $(
+            impl ToModuleResult for $t {
+                type Output = Result<Self>;
+                fn to_module_result(self) -> Self::Output {
+                    Ok(self)
+                }
+            }
+        )*
+    };
 }
+
+impl_to_module_result!(u8, u16, u32, u64, u128, usize);
+impl_to_module_result!(i8, i16, i32, i64, i128, isize);
+impl_to_module_result!(String, &'static str);
 impl<T> ToModuleResult for Result<T> {
     type Output = Self;
     fn to_module_result(self) -> Self::Output {
@@ -84,47 +133,204 @@ impl<O, E: Debug> ToModuleResult for IResult<(), O, E> {
     }
 }
 
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+    static CAUGHT_PANIC: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook which records the message and location of panics that
+/// happen inside `catch_panic`, and defers to the default hook for all others.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANICS.with(Cell::get) {
+                return default_hook(info);
+            }
+            let payload = info.payload();
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                (*s).to_owned()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "<non-string payload>".to_owned()
+            };
+            let location = info
+                .location()
+                .map_or_else(|| "<unknown>".to_owned(), ToString::to_string);
+            CAUGHT_PANIC.with(|caught| *caught.borrow_mut() = Some((message, location)));
+        }));
+    });
+}
+
+/// Runs a single part, converting a panic into `AoCError::Panic`.
+pub fn catch_panic<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    install_panic_hook();
+    let was_catching = CATCHING_PANICS.with(|catching| catching.replace(true));
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANICS.with(|catching| catching.set(was_catching));
+    result.unwrap_or_else(|_| {
+        let (message, location) = CAUGHT_PANIC
+            .with(|caught| caught.borrow_mut().take())
+            .unwrap_or_else(|| ("<unknown>".to_owned(), "<unknown>".to_owned()));
+        Err(AoCError::Panic { message, location })
+    })
+}
+
+/// Time budget for a single part of a day that doesn't specify its own.
+pub const DEFAULT_TIME_BUDGET: Duration = Duration::from_secs(10);
+const PART_STACK_SIZE: usize = 32 * 1024 * 1024;
+
+/// Runs a single part on a worker thread, reporting `AoCError::Timeout` if it
+/// doesn't finish within `budget`. Threads cannot be cancelled, so a part that
+/// timed out keeps running in the background until the process exits.
+pub fn run_part<F>(budget: Duration, f: F) -> Result<String>
+where
+    F: FnOnce() -> Result<String> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let _ = sender.send(catch_panic(f));
+        });
+    if spawned.is_err() {
+        return Err(AoCError::Logic("cannot spawn worker thread"));
+    }
+    match receiver.recv_timeout(budget) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(AoCError::Timeout(budget)),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(AoCError::Logic("worker thread exited without a result"))
+        }
+    }
+}
+
 pub enum Message<'s> {
     Start(&'s str),
     Finish(&'s str, Result<String>),
 }
 
-// Main function
+/// Runs the parts of a day for which the filter returns true, reporting
+/// progress and results through the callback.
+pub type ModuleFn =
+    fn(&str, &mut dyn FnMut(&'static str) -> bool, &mut dyn FnMut(Message<'static>));
+
+/// Metadata and entry point of a single day.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub name: &'static str,
+    pub time_budget: Duration,
+    pub run: ModuleFn,
+    pub solve_part: fn(&str, &str) -> Option<Result<String>>,
+}
+
+impl Day {
+    pub fn input_path(&self) -> String {
+        input_path(self.name)
+    }
+}
+
+impl Debug for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Day")
+            .field("number", &self.number)
+            .field("name", &self.name)
+            .field("time_budget", &self.time_budget)
+            .finish()
+    }
+}
+
+/// Extracts the number from a module name such as `day07`.
+pub const fn day_number(name: &str) -> u8 {
+    let bytes = name.as_bytes();
+    let mut number = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            number = number * 10 + (bytes[i] - b'0');
+        }
+        i += 1;
+    }
+    number
+}
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    crate::DAYS.iter().find(|day| day.number == number)
+}
+
+// Declares the modules of every day, and the table describing them
 #[macro_export]
-macro_rules! generate_main {
+macro_rules! generate_days {
     ($($mod_name:ident)*) => {
         $(
-            mod $mod_name;
+            pub mod $mod_name;
         )*
 
-        fn main() {
-            use module::*;
-            use colored::Colorize;
-            println!("{} {} {} {}", "Advent".bright_red().bold(),
-                "of".bright_white(), "Code".bright_green().bold(), "2019".bright_blue());
-
-            let exclusive_module = get_exclusive_module();
-            let exclusive_module = exclusive_module.as_ref().map(String::as_str);
+        /// Every day, in order.
+        pub static DAYS: &[$crate::module::Day] = &[$(
+            $crate::module::Day {
+                number: $crate::module::day_number(stringify!($mod_name)),
+                name: stringify!($mod_name),
+                time_budget: $mod_name::TIME_BUDGET,
+                run: |input, filter, out| $mod_name::module_filtered(input, filter, out),
+                solve_part: $mod_name::solve_part,
+            },
+        )*];
 
+        #[cfg(test)]
+        mod examples {
             $(
-            if exclusive_module.is_none() || exclusive_module == Some(stringify!($mod_name)) {
-                execute_module(stringify!($mod_name), |input, mut closure| $mod_name::module(input, |msg| {
-                    execute_module_callback(&mut closure, msg)
-                }));
+            #[test]
+            fn $mod_name() {
+                $crate::module::test_examples(stringify!($mod_name), |input, filter, out| {
+                    super::$mod_name::module_filtered(input, filter, out)
+                });
             }
-            )*;
+            )*
         }
     };
 }
 
 pub fn get_exclusive_module() -> Option<String> {
-    std::env::args().nth(1)
+    std::env::args().skip(1).find(|arg| !arg.starts_with("--"))
+}
+
+pub fn input_path(module_name: &str) -> String {
+    format!("./data/{}.txt", module_name)
+}
+
+pub fn profile_input_path(profile: &str, module_name: &str) -> String {
+    format!("./data/{}/{}.txt", profile, module_name)
+}
+
+/// The names of the accounts that have inputs of their own in `./data/<profile>/`.
+/// These are the directories in `./data/`, other than the examples and the
+/// inputs of other years.
+pub fn profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = std::fs::read_dir("./data")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != "examples" && !name.chars().all(|c| c.is_ascii_digit()))
+        .collect();
+    profiles.sort();
+    profiles
 }
 
 pub fn read_module_input(module_name: &'static str) -> std::io::Result<String> {
-    std::fs::read_to_string(format!("./data/{}.txt", module_name)).map(|s| {
-        s.replace("\r\n", "\n")
-    })
+    read_input(&input_path(module_name))
+}
+
+pub fn read_input(path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(path).map(|s| s.replace("\r\n", "\n"))
 }
 
 pub struct Closure<'a> {
@@ -170,12 +376,20 @@ pub fn execute_module_callback(closure: &mut Closure, msg: Message<'static>) {
     }
 }
 pub fn execute_module<F>(module_name: &'static str, executor: F)
+where
+    F: FnOnce(&str, Closure),
+{
+    execute_module_at(module_name, &input_path(module_name), executor)
+}
+
+/// Like `execute_module`, but reads the input from `path`.
+pub fn execute_module_at<F>(module_name: &'static str, path: &str, executor: F)
 where
     F: FnOnce(&str, Closure),
 {
     use colored::Colorize;
     let mut stdout = std::io::stdout();
-    match read_module_input(module_name) {
+    match read_input(path) {
         Ok(input) => {
             let mut last_part = None;
             executor(
@@ -196,3 +410,122 @@ where
         }
     }
 }
+
+/// Runs a module against every example fixture in `./data/examples/<module_name>/`.
+///
+/// Each `<name>.txt` file is an example input, and each `<name>.<part>` file
+/// next to it (e.g. `larger.pt1`) holds the expected answer for that part.
+/// Only parts with an expected answer are executed.
+#[cfg(test)]
+pub fn test_examples<F>(module_name: &'static str, mut executor: F)
+where
+    F: FnMut(&str, &mut dyn FnMut(&'static str) -> bool, &mut dyn FnMut(Message<'static>)),
+{
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    fn trim_newlines(s: &str) -> &str {
+        s.trim_matches(|c| c == '\n' || c == '\r')
+    }
+
+    let dir = Path::new("./data/examples").join(module_name);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+        Err(err) => panic!("cannot read {}: {:?}", dir.display(), err),
+    };
+
+    // Fixture name => (input, part name => expected answer)
+    let mut fixtures = BTreeMap::<String, (Option<String>, BTreeMap<String, String>)>::new();
+    for entry in entries {
+        let path = entry.expect("cannot read directory entry").path();
+        let (name, extension) = match (path.file_stem(), path.extension()) {
+            (Some(name), Some(extension)) => (
+                name.to_string_lossy().into_owned(),
+                extension.to_string_lossy().into_owned(),
+            ),
+            _ => continue,
+        };
+        let contents = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("cannot read {}: {:?}", path.display(), err))
+            .replace("\r\n", "\n");
+        let fixture = fixtures.entry(name).or_default();
+        if extension == "txt" {
+            fixture.0 = Some(contents);
+        } else {
+            let expected = trim_newlines(&contents).to_owned();
+            fixture.1.insert(extension, expected);
+        }
+    }
+
+    let mut failures = Vec::new();
+    for (name, (input, expected)) in fixtures {
+        let input = match input {
+            Some(input) => input,
+            None => panic!("{}/{}: missing {}.txt", module_name, name, name),
+        };
+        if expected.is_empty() {
+            failures.push(format!("{}/{}: no expected answers", module_name, name));
+            continue;
+        }
+
+        let mut results = BTreeMap::new();
+        executor(
+            trim_newlines(&input),
+            &mut |part| expected.contains_key(part),
+            &mut |msg| {
+                if let Message::Finish(part, result) = msg {
+                    results.insert(part.to_owned(), result);
+                }
+            },
+        );
+
+        for (part, expected) in &expected {
+            match results.remove(part) {
+                Some(Ok(actual)) if trim_newlines(&actual) == expected => {}
+                Some(Ok(actual)) => failures.push(format!(
+                    "{}/{} {}: expected {:?}, got {:?}",
+                    module_name,
+                    name,
+                    part,
+                    expected,
+                    trim_newlines(&actual)
+                )),
+                Some(Err(err)) => failures.push(format!(
+                    "{}/{} {}: error {:?}",
+                    module_name, name, part, err
+                )),
+                None => failures.push(format!("{}/{}: no part named {}", module_name, name, part)),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn panics_are_caught() {
+    assert_eq!(catch_panic(|| Ok(5)).ok(), Some(5));
+    match catch_panic::<(), _>(|| panic!("part {} failed", 2)) {
+        Err(AoCError::Panic { message, location }) => {
+            assert_eq!(message, "part 2 failed");
+            assert!(location.starts_with("src/module.rs:"), "{}", location);
+        }
+        _ => panic!("expected AoCError::Panic"),
+    }
+    assert_eq!(catch_panic(|| Ok(7)).ok(), Some(7));
+}
+
+#[test]
+fn parts_time_out() {
+    let budget = Duration::from_millis(50);
+    let result = run_part(budget, || Ok("done".to_owned()));
+    assert_eq!(result.ok(), Some("done".to_owned()));
+    match run_part(budget, || {
+        thread::sleep(Duration::from_secs(1));
+        Ok(String::new())
+    }) {
+        Err(AoCError::Timeout(timeout)) => assert_eq!(timeout, budget),
+        _ => panic!("expected AoCError::Timeout"),
+    }
+}
@@ -0,0 +1,129 @@
+//! Reading the block letters that some puzzles draw as their answer.
+#![allow(dead_code)]
+
+use crate::error::AoCError;
+use crate::mat2::Mat2;
+use crate::module::Result;
+use crate::vec2::Vec2us;
+
+/// The letters are drawn in cells this wide, including the column between
+/// letters, which only `Y` uses.
+const CELL_WIDTH: usize = 5;
+const HEIGHT: usize = 6;
+const FIRST_COLUMN: u32 = 0b00001_00001_00001_00001_00001_00001;
+
+#[rustfmt::skip]
+const LETTERS: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The lit pixels of a cell as a bitmask, row by row.
+fn glyph_bits(rows: &[&str; HEIGHT]) -> u32 {
+    let mut bits = 0;
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == '#' {
+                bits |= 1 << (y * CELL_WIDTH + x);
+            }
+        }
+    }
+    bits
+}
+
+/// Reads the capital letters drawn in `image`, which has to be 6 pixels high.
+/// The letters are 4 pixels wide (`Y` is 5) and start every 5 columns from the
+/// first lit column. `is_lit` tells which pixels are drawn.
+pub fn read_letters<T, F>(image: &Mat2<T>, mut is_lit: F) -> Result<String>
+where
+    T: Clone,
+    F: FnMut(&T) -> bool,
+{
+    if image.height() != HEIGHT {
+        return Err(AoCError::IncorrectInput("letters have to be 6 pixels high"));
+    }
+    let mut cell = |x: usize| {
+        let mut bits = 0;
+        for y in 0..HEIGHT {
+            for dx in 0..CELL_WIDTH {
+                if x + dx < image.width() && is_lit(&image[Vec2us::new(x + dx, y)]) {
+                    bits |= 1 << (y * CELL_WIDTH + dx);
+                }
+            }
+        }
+        bits
+    };
+    let letter = |bits: u32| {
+        LETTERS
+            .iter()
+            .find(|(_, rows)| glyph_bits(rows) == bits)
+            .map(|&(letter, _)| letter)
+    };
+
+    // The cells are aligned to the first lit column, or the one before it
+    // for letters like `I` of which the first column is blank
+    let first = match (0..image.width()).find(|&x| cell(x) & FIRST_COLUMN != 0) {
+        Some(first) => first,
+        None => return Err(AoCError::IncorrectInput("no letters in image")),
+    };
+    let start = if first > 0 && letter(cell(first)).is_none() {
+        first - 1
+    } else {
+        first
+    };
+
+    let mut text = String::new();
+    for x in (start..image.width()).step_by(CELL_WIDTH) {
+        let bits = cell(x);
+        if bits == 0 {
+            continue;
+        }
+        text.push(letter(bits).ok_or(AoCError::IncorrectInput("unrecognized letter"))?);
+    }
+    Ok(text)
+}
+
+#[test]
+fn ocr() {
+    let image = Mat2::from_text(
+        "\
+...##.####.#...##..#..
+....#....#.#...##..#..
+....#...#...#.#.####..
+....#..#.....#..#..#..
+.#..#.#......#..#..#..
+..##..####...#..#..#..",
+        |c| Ok(c == '#'),
+    )
+    .unwrap();
+    assert_eq!(read_letters(&image, |&lit| lit).unwrap(), "JZYH");
+    // The first column of `I` is blank
+    let image = Mat2::from_text(
+        ".###.#...\n..#..#...\n..#..#...\n..#..#...\n..#..#...\n.###.####",
+        |c| Ok(c == '#'),
+    )
+    .unwrap();
+    assert_eq!(read_letters(&image, |&lit| lit).unwrap(), "IL");
+
+    let blank = Mat2::new(false, Vec2us::new(10, HEIGHT));
+    assert!(read_letters(&blank, |&lit| lit).is_err());
+    let mut unknown = blank;
+    unknown[Vec2us::new(0, 0)] = true;
+    assert!(read_letters(&unknown, |&lit| lit).is_err());
+}
@@ -75,7 +75,7 @@ pub fn eof<T: nom::InputLength, E: ParseError<T>>(input: T) -> IResult<T, (), E>
 
 pub fn is_ascii_printable(c: char) -> bool {
     let value = c as u64;
-    value >= 32 && value < 127
+    (32..127).contains(&value)
 }
 
 pub fn printable0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
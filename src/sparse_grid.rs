@@ -0,0 +1,199 @@
+// Grid without a fixed size, backed by a hash map
+#![allow(dead_code)]
+use crate::direction::{Direction, MoveInDirection};
+use crate::mat2::Mat2;
+use crate::vec2::{AabbIteratorEx, Vec2i64, Vec2us};
+use crate::HashMap;
+use std::iter::FromIterator;
+use std::ops::Index;
+
+/// A grid of which only the cells that were set are stored, so it can grow in
+/// any direction. Keeps track of the bounding box of its cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2i64, T>,
+    bounds: Option<(Vec2i64, Vec2i64)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    #[rustfmt::skip] #[inline(always)] pub fn len(&self) -> usize { self.cells.len() }
+    #[rustfmt::skip] #[inline(always)] pub fn is_empty(&self) -> bool { self.cells.is_empty() }
+
+    /// The smallest and largest coordinates of the cells, both inclusive.
+    pub fn bounds(&self) -> Option<(Vec2i64, Vec2i64)> {
+        self.bounds
+    }
+
+    pub fn get(&self, pos: Vec2i64) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2i64) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Vec2i64) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Sets the cell, returning what it was set to before.
+    pub fn insert(&mut self, pos: Vec2i64, item: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Vec2i64::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Vec2i64::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+            None => (pos, pos),
+        });
+        self.cells.insert(pos, item)
+    }
+
+    pub fn remove(&mut self, pos: Vec2i64) -> Option<T> {
+        let item = self.cells.remove(&pos)?;
+        // The bounds can only shrink if the cell was on their edge
+        if let Some((min, max)) = self.bounds {
+            if pos.x == min.x || pos.y == min.y || pos.x == max.x || pos.y == max.y {
+                self.bounds = self.cells.keys().cloned().aabb();
+            }
+        }
+        Some(item)
+    }
+
+    /// Iterates over the cells in row-major order, from the top left.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2i64, &T)> {
+        let mut cells: Vec<_> = self.cells.iter().map(|(&pos, item)| (pos, item)).collect();
+        cells.sort_unstable_by_key(|(pos, _)| (pos.y, pos.x));
+        cells.into_iter()
+    }
+
+    /// The cells that are set next to `pos`, along with the direction they're in.
+    pub fn neighbors(&self, pos: Vec2i64) -> impl Iterator<Item = (Direction, Vec2i64, &T)> {
+        Direction::each().filter_map(move |dir| {
+            let next = pos.step_in_direction_checked(dir)?;
+            self.cells.get(&next).map(|item| (dir, next, item))
+        })
+    }
+
+    /// Renders the bounding box, with a line per row which each end with a
+    /// newline. `cell` is given `None` for the cells that aren't set.
+    pub fn render<F>(&self, mut cell: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+    {
+        let mut s = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    s.push(cell(self.cells.get(&Vec2i64::new(x, y))));
+                }
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Copies the bounding box into a `Mat2`, in which the cells that aren't set
+    /// are `default`. Returns the offset that has to be added to a position in
+    /// the `Mat2` to get the position in the grid.
+    pub fn to_mat2(&self, default: T) -> (Mat2<T>, Vec2i64) {
+        let (min, max) = match self.bounds {
+            Some(bounds) => bounds,
+            None => return (Mat2::new(default, Vec2us::new(0, 0)), Vec2i64::default()),
+        };
+        let size = Vec2us::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+        let mut mat = Mat2::new(default, size);
+        for (&pos, item) in &self.cells {
+            mat[Vec2us::new((pos.x - min.x) as usize, (pos.y - min.y) as usize)] = item.clone();
+        }
+        (mat, min)
+    }
+
+    /// Sets a cell for every cell of the `Mat2`, at its position plus `offset`.
+    pub fn from_mat2(mat: &Mat2<T>, offset: Vec2i64) -> Self {
+        mat.iter()
+            .map(|(pos, item)| {
+                let pos = Vec2i64::new(pos.x as i64 + offset.x, pos.y as i64 + offset.y);
+                (pos, item.clone())
+            })
+            .collect()
+    }
+}
+
+impl<T> Index<Vec2i64> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2i64) -> &T {
+        &self.cells[&pos]
+    }
+}
+
+impl<T> Extend<(Vec2i64, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Vec2i64, T)>>(&mut self, iter: I) {
+        for (pos, item) in iter {
+            self.insert(pos, item);
+        }
+    }
+}
+
+impl<T> FromIterator<(Vec2i64, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vec2i64, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[test]
+fn sparse_grid() {
+    let mut grid: SparseGrid<char> = vec![
+        (Vec2i64::new(1, -1), 'a'),
+        (Vec2i64::new(-1, 0), 'b'),
+        (Vec2i64::new(0, 0), 'c'),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        grid.bounds(),
+        Some((Vec2i64::new(-1, -1), Vec2i64::new(1, 0)))
+    );
+    let order: String = grid.iter().map(|(_, &c)| c).collect();
+    assert_eq!(order, "abc");
+    assert_eq!(grid.render(|c| c.cloned().unwrap_or(' ')), "  a\nbc \n");
+
+    let neighbors: Vec<_> = grid
+        .neighbors(Vec2i64::new(0, 0))
+        .map(|(dir, _, &c)| (dir, c))
+        .collect();
+    assert_eq!(neighbors, [(Direction::West, 'b')]);
+
+    let (mat, offset) = grid.to_mat2('.');
+    assert_eq!(
+        (mat.size(), offset),
+        (Vec2us::new(3, 2), Vec2i64::new(-1, -1))
+    );
+    assert_eq!(mat[Vec2us::new(2, 0)], 'a');
+    let round_trip = SparseGrid::from_mat2(&mat, offset);
+    assert_eq!(round_trip.len(), 6);
+    assert_eq!(round_trip.bounds(), grid.bounds());
+
+    assert_eq!(grid.remove(Vec2i64::new(1, -1)), Some('a'));
+    assert_eq!(
+        grid.bounds(),
+        Some((Vec2i64::new(-1, 0), Vec2i64::new(0, 0)))
+    );
+}
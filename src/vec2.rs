@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use num::integer::Integer;
 use num::traits::{
     identities::{One, Zero},
     sign::{Signed, Unsigned},
@@ -8,13 +9,14 @@ use num::traits::{
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt;
-use std::iter::{ExactSizeIterator, IntoIterator, Iterator, TrustedLen};
+use std::iter::{ExactSizeIterator, IntoIterator, Iterator};
 use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 use std::str::FromStr;
 
 pub type Vec2i = Vec2<i32>;
+pub type Vec2i64 = Vec2<i64>;
 pub type Vec2us = Vec2<usize>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Hash)]
@@ -54,6 +56,16 @@ impl<T> Vec2<T> {
         f(&self.x, &other.x) || f(&self.y, &other.y)
     }
 
+    pub fn cast<U>(self) -> Vec2<U>
+    where
+        U: From<T>,
+    {
+        Vec2 {
+            x: U::from(self.x),
+            y: U::from(self.y),
+        }
+    }
+
     pub fn convert<U>(self) -> Result<Vec2<U>, <U as TryFrom<T>>::Error>
     where
         U: TryFrom<T>,
@@ -85,14 +97,64 @@ where
     }
 }
 
-pub auto trait IsNotVec2 {}
-impl<T> !IsNotVec2 for Vec2<T> {}
-pub auto trait NotEq {}
-impl<X> !NotEq for (X, X) {}
+impl<T> Vec2<T>
+where
+    T: PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// The number of orthogonal steps between the two positions.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        let delta = self.delta(other);
+        delta.x + delta.y
+    }
+
+    /// The number of steps between the two positions when diagonal steps
+    /// are allowed too.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        let delta = self.delta(other);
+        if delta.x >= delta.y {
+            delta.x
+        } else {
+            delta.y
+        }
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: Neg<Output = T>,
+{
+    /// Rotates a quarter turn clockwise around the origin, with `y` pointing
+    /// down like `Direction` does.
+    pub fn rotate_clockwise(self) -> Self {
+        Vec2::new(-self.y, self.x)
+    }
+    /// Rotates a quarter turn counterclockwise around the origin, with `y`
+    /// pointing down like `Direction` does.
+    pub fn rotate_counterclockwise(self) -> Self {
+        Vec2::new(self.y, -self.x)
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: Integer + Clone,
+{
+    /// The shortest vector with the same direction that still has integer
+    /// coordinates, found by dividing by the GCD of the coordinates. The zero
+    /// vector stays as is.
+    pub fn normalized(self) -> Self {
+        let gcd = self.x.gcd(&self.y);
+        if gcd.is_zero() {
+            self
+        } else {
+            Vec2::new(self.x / gcd.clone(), self.y / gcd)
+        }
+    }
+}
 
 impl<T> From<T> for Vec2<T>
 where
-    T: Clone + IsNotVec2,
+    T: Clone,
 {
     fn from(value: T) -> Self {
         Vec2 {
@@ -110,40 +172,26 @@ impl<T> From<(T, T)> for Vec2<T> {
     }
 }
 
-impl<T, U> From<Vec2<U>> for Vec2<T>
-where
-    T: From<U>,
-    (T, U): NotEq,
-{
-    fn from(value: Vec2<U>) -> Self {
-        Vec2 {
-            x: T::from(value.x),
-            y: T::from(value.y),
-        }
-    }
-}
-
-impl<T, U> TryFrom<Vec2<U>> for Vec2<T>
+impl<T> Vec2<T>
 where
-    T: TryFrom<U>,
-    (T, U): NotEq,
+    T: Mul,
+    T::Output: Add,
 {
-    type Error = <T as TryFrom<U>>::Error;
-    fn try_from(value: Vec2<U>) -> Result<Vec2<T>, Self::Error> {
-        Ok(Vec2 {
-            x: T::try_from(value.x)?,
-            y: T::try_from(value.y)?,
-        })
+    pub fn dot(self, other: Self) -> <T::Output as Add>::Output {
+        self.x * other.x + self.y * other.y
     }
 }
 
 impl<T> Vec2<T>
 where
     T: Mul,
-    T::Output: Add,
+    T::Output: Sub,
 {
-    pub fn dot(self, other: Self) -> <T::Output as Add>::Output {
-        self.x * other.x + self.y * other.y
+    /// The z coordinate of the cross product of the vectors extended to 3D,
+    /// which is positive when `other` is clockwise from `self` (with `y`
+    /// pointing down).
+    pub fn cross(self, other: Self) -> <T::Output as Sub>::Output {
+        self.x * other.y - self.y * other.x
     }
 }
 
@@ -269,9 +317,7 @@ where
             }
         }
 
-        if let Err(e) = T::from_str_radix("", radix) {
-            return Err(e);
-        }
+        T::from_str_radix("", radix)?;
         unreachable!()
     }
 }
@@ -383,7 +429,6 @@ impl<T> ExactSizeIterator for Vec2Iter<T> {
         }
     }
 }
-unsafe impl<T> TrustedLen for Vec2Iter<T> {}
 
 pub trait AabbIteratorEx<T>: Iterator {
     fn aabb(self) -> Option<(Vec2<T>, Vec2<T>)>;
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use num::integer::Integer;
 use num::traits::{
     identities::{One, Zero},
     sign::{Signed, Unsigned},
@@ -70,6 +71,83 @@ impl<T> Vec3<T> {
     }
 }
 
+impl<T> Vec3<T>
+where
+    T: PartialOrd + Sub<Output = T>,
+{
+    pub fn delta(self, other: Self) -> Self {
+        let delta = |a: T, b: T| if a >= b { a - b } else { b - a };
+        Vec3 {
+            x: delta(self.x, other.x),
+            y: delta(self.y, other.y),
+            z: delta(self.z, other.z),
+        }
+    }
+}
+
+impl<T> Vec3<T>
+where
+    T: PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// The number of orthogonal steps between the two positions.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        let delta = self.delta(other);
+        delta.x + delta.y + delta.z
+    }
+
+    /// The number of steps between the two positions when diagonal steps
+    /// are allowed too.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        let delta = self.delta(other);
+        let max = if delta.x >= delta.y { delta.x } else { delta.y };
+        if max >= delta.z {
+            max
+        } else {
+            delta.z
+        }
+    }
+}
+
+impl<T> Vec3<T>
+where
+    T: Mul<Output = T> + Sub<Output = T> + Add<Output = T> + Clone,
+{
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Self) -> Self {
+        let Vec3 { x, y, z } = self;
+        let Vec3 {
+            x: ox,
+            y: oy,
+            z: oz,
+        } = other;
+        Vec3 {
+            x: y.clone() * oz.clone() - z.clone() * oy.clone(),
+            y: z * ox.clone() - x.clone() * oz,
+            z: x * oy - y * ox,
+        }
+    }
+}
+
+impl<T> Vec3<T>
+where
+    T: Integer + Clone,
+{
+    /// The shortest vector with the same direction that still has integer
+    /// coordinates, found by dividing by the GCD of the coordinates. The zero
+    /// vector stays as is.
+    pub fn normalized(self) -> Self {
+        let gcd = self.x.gcd(&self.y).gcd(&self.z);
+        if gcd.is_zero() {
+            self
+        } else {
+            Vec3::new(self.x / gcd.clone(), self.y / gcd.clone(), self.z / gcd)
+        }
+    }
+}
+
 impl<T> From<T> for Vec3<T>
 where
     T: Clone,
@@ -227,9 +305,7 @@ where
             }
         }
 
-        if let Err(e) = T::from_str_radix("", radix) {
-            return Err(e);
-        }
+        T::from_str_radix("", radix)?;
         unreachable!()
     }
 }